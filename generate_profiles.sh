@@ -6,8 +6,8 @@ mkdir -p profiling-data
 
 for i in $(seq -w 1 25) 
 do 
-    if test -f "./target/release/2023_$i"; then
-        CMD="./target/release/2023_$i --input inputs/real/2023_$i"
+    if test -f "inputs/real/2023_$i"; then
+        CMD="./target/release/aoc run $i"
         perf record -g -F max $CMD
         perf script -F +pid > profiling-data/2023_$i.perf
         rm perf.data
@@ -1,17 +1,17 @@
 set -e
 
-HYPERFINE_RUN_ARGS="--warmup=10 --runs 50"
+BENCH_ARGS="--warmup 10 --bench 50"
 
 for i in $(seq -w 1 25) 
 do 
-    if test -f "./target/release/2023_$i"; then
-        CMD="./target/release/2023_$i --input inputs/real/2023_$i"
+    if test -f "inputs/real/2023_$i"; then
+        CMD="./target/release/aoc run $i"
         echo ""
         echo "2023 Day $i"
         $CMD
         echo ""
         # Main benchmarking
-        hyperfine $HYPERFINE_RUN_ARGS -u millisecond --style basic "$CMD" 2>/dev/null
+        $CMD $BENCH_ARGS
         
         # CPU energy usage benchmark
         CPU_JOULES_ITER=20
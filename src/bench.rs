@@ -0,0 +1,99 @@
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+/// Summary statistics over repeated timings of one step.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Stats {
+    pub iterations: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub stddev: Duration,
+}
+
+impl Stats {
+    /// Computes statistics over `samples`, which must not be empty.
+    pub fn from_samples(samples: &mut [Duration]) -> Stats {
+        assert!(!samples.is_empty(), "no samples");
+
+        samples.sort_unstable();
+
+        let n = samples.len();
+        let median = if n % 2 == 1 {
+            samples[n / 2]
+        } else {
+            (samples[n / 2 - 1] + samples[n / 2]) / 2
+        };
+
+        let mean = samples.iter().sum::<Duration>() / n as u32;
+
+        let variance = samples
+            .iter()
+            .map(|s| (s.as_secs_f64() - mean.as_secs_f64()).powi(2))
+            .sum::<f64>()
+            / n as f64;
+
+        Stats {
+            iterations: n,
+            min: samples[0],
+            median,
+            mean,
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+        }
+    }
+}
+
+/// Number of warmup and timed runs for each step.
+#[derive(Clone, Copy, Debug)]
+pub struct BenchOptions {
+    pub warmup: usize,
+    pub iterations: usize,
+}
+
+/// Times `f` once per iteration, after discarding `warmup` untimed runs.
+/// There must be at least one iteration. Returns the result of the last run
+/// along with the timings.
+pub fn measure<T>(opts: BenchOptions, mut f: impl FnMut() -> T) -> (Stats, T) {
+    for _ in 0..opts.warmup {
+        black_box(f());
+    }
+
+    let mut samples = Vec::with_capacity(opts.iterations);
+    let mut last = None;
+    for _ in 0..opts.iterations {
+        let start = Instant::now();
+        let res = black_box(f());
+        samples.push(start.elapsed());
+        last = Some(res);
+    }
+
+    let last = last.expect("no iterations");
+    (Stats::from_samples(&mut samples), last)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stats() {
+        let mut samples = [4, 1, 3, 2].map(Duration::from_micros);
+        let stats = Stats::from_samples(&mut samples);
+
+        assert_eq!(stats.iterations, 4);
+        assert_eq!(stats.min, Duration::from_micros(1));
+        assert_eq!(stats.median, Duration::from_nanos(2500));
+        assert_eq!(stats.mean, Duration::from_nanos(2500));
+        // sqrt(1.25) us
+        assert_eq!(stats.stddev.as_nanos(), 1118);
+    }
+
+    #[test]
+    fn test_stats_odd_count() {
+        let mut samples = [5, 9, 1].map(Duration::from_millis);
+        let stats = Stats::from_samples(&mut samples);
+
+        assert_eq!(stats.median, Duration::from_millis(5));
+        assert_eq!(stats.mean, Duration::from_millis(5));
+    }
+}
@@ -0,0 +1,1108 @@
+use advent_of_code_2023::bench::{BenchOptions, Stats};
+use advent_of_code_2023::days::{day05, day12, day17, day19, day20, day23, day25};
+use advent_of_code_2023::grid_util::RenderFormat;
+use advent_of_code_2023::interval_set::IntervalSet;
+use advent_of_code_2023::nonogram::{self, Nonogram};
+use advent_of_code_2023::verify::{check_answers, read_answers, Verdict};
+use advent_of_code_2023::{
+    days, AlmanacArgs, BenchResult, Cli, Command, CrucibleArgs, Format, GraphArgs, GraphDay,
+    NonogramArgs, Parser, PulsesArgs, RunArgs, SpringsArgs, VerifyArgs, WorkflowsArgs,
+};
+use anyhow::{bail, Context, Result};
+use num::One;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::io::{self, BufWriter, Read, Write};
+use std::ops::Range;
+use std::path::Path;
+use std::process::ExitCode;
+
+#[derive(Serialize)]
+struct JsonAnswer {
+    day: u8,
+    input: String,
+    part: u8,
+    answer: String,
+    parse_time_ns: u128,
+    solve_time_ns: u128,
+}
+
+#[derive(Serialize)]
+struct JsonBench {
+    day: u8,
+    input: String,
+    step: String,
+    iterations: usize,
+    min_ns: u128,
+    median_ns: u128,
+    mean_ns: u128,
+    stddev_ns: u128,
+}
+
+impl JsonBench {
+    fn new(day: u8, input: &str, step: String, stats: &Stats) -> JsonBench {
+        JsonBench {
+            day,
+            input: input.to_string(),
+            step,
+            iterations: stats.iterations,
+            min_ns: stats.min.as_nanos(),
+            median_ns: stats.median.as_nanos(),
+            mean_ns: stats.mean.as_nanos(),
+            stddev_ns: stats.stddev.as_nanos(),
+        }
+    }
+}
+
+const STDIN_PATH: &str = "-";
+const STDOUT_PATH: &str = "-";
+
+fn read_input(path: &str) -> Result<String> {
+    if path == STDIN_PATH {
+        let mut inp = String::new();
+        io::stdin()
+            .read_to_string(&mut inp)
+            .context("can't read input from stdin")?;
+        Ok(inp)
+    } else {
+        fs::read_to_string(path).with_context(|| format!("can't open input file {}", path))
+    }
+}
+
+fn display_path(path: &str) -> &str {
+    if path == STDIN_PATH {
+        "<stdin>"
+    } else {
+        path
+    }
+}
+
+fn bench_steps(result: &BenchResult) -> Vec<(String, &Stats)> {
+    std::iter::once(("parse".to_string(), &result.parse))
+        .chain(
+            result
+                .parts
+                .iter()
+                .zip(1..)
+                .map(|(stats, part)| (format!("part{}", part), stats)),
+        )
+        .collect()
+}
+
+fn print_bench(title: &str, opts: BenchOptions, result: &BenchResult) {
+    println!(
+        "{} ({} runs, {} warmup)",
+        title, opts.iterations, opts.warmup
+    );
+    println!(
+        "{:<8}{:>12}{:>12}{:>12}{:>12}",
+        "step", "min", "median", "mean", "stddev"
+    );
+    for (step, stats) in bench_steps(result) {
+        println!(
+            "{:<8}{:>12}{:>12}{:>12}{:>12}",
+            step,
+            format!("{:.2?}", stats.min),
+            format!("{:.2?}", stats.median),
+            format!("{:.2?}", stats.mean),
+            format!("{:.2?}", stats.stddev)
+        );
+    }
+}
+
+fn run(args: &RunArgs, format: Format) -> Result<()> {
+    let selected = args.selected_days();
+
+    if !args.input.is_empty() && selected.len() > 1 {
+        bail!("--input can only be used when running a single day");
+    }
+    if args.input.iter().filter(|&path| path == STDIN_PATH).count() > 1 {
+        bail!("stdin can only be used as one of the inputs");
+    }
+    if args.render.is_some() && (selected.len() > 1 || args.input.len() > 1) {
+        bail!("--render can only be used with a single day and input");
+    }
+
+    let show_titles = selected.len() > 1 || args.input.len() > 1;
+
+    let mut json_answers = vec![];
+    let mut json_benches = vec![];
+
+    for &day in &selected {
+        let solution = days::get(day).with_context(|| format!("no solution for day {}", day))?;
+
+        for path in args.input_paths(day) {
+            let inp = read_input(&path)?;
+            let path = display_path(&path);
+
+            let title = if args.input.len() > 1 {
+                format!("2023 Day {:02} - {}", day, path)
+            } else {
+                format!("2023 Day {:02}", day)
+            };
+
+            if let Some(opts) = args.bench_options() {
+                let result = solution
+                    .bench(&inp, opts)
+                    .with_context(|| format!("day {} failed on {}", day, path))?;
+
+                match format {
+                    Format::Text => print_bench(&title, opts, &result),
+                    Format::Json => json_benches.extend(
+                        bench_steps(&result)
+                            .into_iter()
+                            .map(|(step, stats)| JsonBench::new(day, path, step, stats)),
+                    ),
+                }
+                continue;
+            }
+
+            if format == Format::Text && show_titles {
+                println!("{}", title);
+            }
+            let result = solution
+                .run(&inp)
+                .with_context(|| format!("day {} failed on {}", day, path))?;
+
+            for part in result.parts {
+                match format {
+                    Format::Text => println!("{}", part.answer),
+                    Format::Json => json_answers.push(JsonAnswer {
+                        day,
+                        input: path.to_string(),
+                        part: part.part,
+                        answer: part.answer,
+                        parse_time_ns: result.parse_time.as_nanos(),
+                        solve_time_ns: part.solve_time.as_nanos(),
+                    }),
+                }
+            }
+
+            if let Some(render_path) = &args.render {
+                let rendered = solution
+                    .render(&inp, RenderFormat::from_path(render_path))?
+                    .with_context(|| format!("day {} has nothing to render", day))?;
+                fs::write(render_path, rendered)
+                    .with_context(|| format!("can't write {}", render_path))?;
+            }
+        }
+    }
+
+    if format == Format::Json {
+        if args.bench.is_some() {
+            println!("{}", serde_json::to_string_pretty(&json_benches)?);
+        } else {
+            println!("{}", serde_json::to_string_pretty(&json_answers)?);
+        }
+    }
+
+    Ok(())
+}
+
+#[derive(Clone, Copy, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum Status {
+    Pass,
+    Fail,
+    Missing,
+}
+
+/// Outcome of verifying one part, or a whole day if it couldn't be run.
+#[derive(Serialize)]
+struct VerifyEntry {
+    day: u8,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    part: Option<u8>,
+    status: Status,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    expected: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    actual: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    detail: Option<String>,
+}
+
+impl VerifyEntry {
+    fn day(day: u8, status: Status, detail: String) -> VerifyEntry {
+        VerifyEntry {
+            day,
+            part: None,
+            status,
+            expected: None,
+            actual: None,
+            detail: Some(detail),
+        }
+    }
+}
+
+impl fmt::Display for VerifyEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Day {:02}", self.day)?;
+        if let Some(part) = self.part {
+            write!(f, " part {}", part)?;
+        }
+
+        let status = match self.status {
+            Status::Pass => "pass",
+            Status::Fail => "FAIL",
+            Status::Missing => "missing",
+        };
+        write!(f, ": {}", status)?;
+
+        if let (Some(expected), Some(actual)) = (&self.expected, &self.actual) {
+            write!(f, " (expected {}, got {})", expected, actual)?;
+        }
+        if let Some(detail) = &self.detail {
+            write!(f, " ({})", detail)?;
+        }
+        Ok(())
+    }
+}
+
+fn verify_day(day: u8, path: &str) -> Result<Vec<VerifyEntry>> {
+    let solution = days::get(day).with_context(|| format!("no solution for day {}", day))?;
+
+    if !Path::new(path).exists() {
+        return Ok(vec![VerifyEntry::day(
+            day,
+            Status::Missing,
+            format!("no input at {}", path),
+        )]);
+    }
+
+    let Ok(expected) = read_answers(path) else {
+        return Ok(vec![VerifyEntry::day(
+            day,
+            Status::Missing,
+            format!("no answers for {}", path),
+        )]);
+    };
+
+    let inp = read_input(path)?;
+    let result = match solution.run(&inp) {
+        Ok(result) => result,
+        Err(e) => {
+            return Ok(vec![VerifyEntry::day(
+                day,
+                Status::Fail,
+                format!("{:#}", e),
+            )])
+        }
+    };
+
+    let actual = result
+        .parts
+        .iter()
+        .map(|part| part.answer.clone())
+        .collect::<Vec<_>>();
+
+    Ok(check_answers(&expected, &actual)
+        .into_iter()
+        .zip(result.parts)
+        .map(|(verdict, part)| {
+            let (status, expected) = match verdict {
+                Verdict::Pass => (Status::Pass, None),
+                Verdict::Fail { expected, .. } => (Status::Fail, Some(expected)),
+                Verdict::Missing => (Status::Missing, None),
+            };
+            VerifyEntry {
+                day,
+                part: Some(part.part),
+                status,
+                actual: expected.is_some().then_some(part.answer),
+                expected,
+                detail: None,
+            }
+        })
+        .collect())
+}
+
+fn verify(args: &VerifyArgs, format: Format) -> Result<ExitCode> {
+    let mut entries = vec![];
+
+    for day in args.selected_days() {
+        let day_entries = verify_day(day, &args.input_path(day))?;
+
+        if format == Format::Text {
+            for entry in &day_entries {
+                println!("{}", entry);
+            }
+        }
+        entries.extend(day_entries);
+    }
+
+    let count = |status| entries.iter().filter(|e| e.status == status).count();
+    let failed = count(Status::Fail);
+
+    match format {
+        Format::Text => println!(
+            "{} passed, {} failed, {} missing",
+            count(Status::Pass),
+            failed,
+            count(Status::Missing)
+        ),
+        Format::Json => println!("{}", serde_json::to_string_pretty(&entries)?),
+    }
+
+    Ok(if failed > 0 {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    })
+}
+
+#[derive(Serialize)]
+struct JsonAlmanacQuery {
+    category: String,
+    query: String,
+    seeds: Vec<String>,
+}
+
+#[derive(Serialize)]
+struct JsonPiece {
+    from: i64,
+    /// Exclusive, or missing for the last piece, which has no end
+    #[serde(skip_serializing_if = "Option::is_none")]
+    to: Option<i64>,
+    offset: i64,
+}
+
+fn format_interval(range: &Range<i64>) -> String {
+    if range.end == range.start + 1 {
+        range.start.to_string()
+    } else {
+        format!("{}..{}", range.start, range.end)
+    }
+}
+
+fn almanac(args: &AlmanacArgs, format: Format) -> Result<()> {
+    let inp = read_input(&args.input)?;
+    let data = inp
+        .parse::<day05::Data>()
+        .map_err(|e| e.locate(&inp))
+        .with_context(|| format!("invalid almanac in {}", display_path(&args.input)))?;
+
+    if args.table {
+        let table = data.location_map();
+        match format {
+            Format::Text => print!("{}", table),
+            Format::Json => {
+                let pieces = table
+                    .pieces()
+                    .map(|(range, offset)| JsonPiece {
+                        from: range.start,
+                        to: (range.end != i64::MAX).then_some(range.end),
+                        offset,
+                    })
+                    .collect::<Vec<_>>();
+                println!("{}", serde_json::to_string_pretty(&pieces)?);
+            }
+        }
+        return Ok(());
+    }
+
+    let mut json_queries = vec![];
+
+    for query in &args.values {
+        let seeds = data
+            .seeds_for(&args.from, &IntervalSet::from(query.clone()))
+            .with_context(|| {
+                format!(
+                    "no category '{}' in almanac, expected one of: {}",
+                    args.from,
+                    data.categories().collect::<Vec<_>>().join(", ")
+                )
+            })?;
+        let seeds = seeds
+            .ranges()
+            .iter()
+            .map(format_interval)
+            .collect::<Vec<_>>();
+
+        match format {
+            Format::Text if seeds.is_empty() => {
+                println!("{} {}: no seeds", args.from, format_interval(query))
+            }
+            Format::Text => println!(
+                "{} {}: seeds {}",
+                args.from,
+                format_interval(query),
+                seeds.join(", ")
+            ),
+            Format::Json => json_queries.push(JsonAlmanacQuery {
+                category: args.from.clone(),
+                query: format_interval(query),
+                seeds,
+            }),
+        }
+    }
+
+    if format == Format::Json {
+        println!("{}", serde_json::to_string_pretty(&json_queries)?);
+    }
+
+    Ok(())
+}
+
+#[derive(Serialize)]
+struct JsonRegion<'a> {
+    path: &'a [&'a str],
+    ranges: BTreeMap<&'a str, (i64, i64)>,
+}
+
+#[derive(Serialize)]
+struct JsonPartCheck<'a> {
+    part: String,
+    accepted: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    path: Option<&'a [&'a str]>,
+}
+
+fn format_part(dims: &[&str], ratings: &[i64]) -> String {
+    let ratings = dims
+        .iter()
+        .zip(ratings)
+        .map(|(dim, value)| format!("{}={}", dim, value))
+        .collect::<Vec<_>>();
+    format!("{{{}}}", ratings.join(","))
+}
+
+#[derive(Serialize)]
+struct JsonRuleRef<'a> {
+    workflow: &'a str,
+    /// 1-based, or missing for the final unconditional rule
+    #[serde(skip_serializing_if = "Option::is_none")]
+    rule: Option<usize>,
+    text: &'a str,
+}
+
+impl<'a> From<&'a day19::RuleRef<'a>> for JsonRuleRef<'a> {
+    fn from(rule: &'a day19::RuleRef<'a>) -> JsonRuleRef<'a> {
+        JsonRuleRef {
+            workflow: rule.workflow,
+            rule: rule.index.map(|idx| idx + 1),
+            text: &rule.text,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct JsonLintReport<'a> {
+    unreachable_workflows: &'a [&'a str],
+    dead_rules: Vec<JsonRuleRef<'a>>,
+    redundant_rules: Vec<JsonRuleRef<'a>>,
+    cycles: &'a [Vec<&'a str>],
+}
+
+fn lint_workflows(data: &day19::Data, format: Format) -> Result<ExitCode> {
+    let report = data.lint();
+
+    match format {
+        Format::Text if report.is_clean() => println!("no problems found"),
+        Format::Text => print!("{}", report),
+        Format::Json => {
+            let json_report = JsonLintReport {
+                unreachable_workflows: &report.unreachable_workflows,
+                dead_rules: report.dead_rules.iter().map(JsonRuleRef::from).collect(),
+                redundant_rules: report
+                    .redundant_rules
+                    .iter()
+                    .map(JsonRuleRef::from)
+                    .collect(),
+                cycles: &report.cycles,
+            };
+            println!("{}", serde_json::to_string_pretty(&json_report)?);
+        }
+    }
+
+    Ok(if report.is_clean() {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    })
+}
+
+fn workflows(args: &WorkflowsArgs, format: Format) -> Result<ExitCode> {
+    let inp = read_input(&args.input)?;
+    let data = day19::parse(&inp)
+        .map_err(|e| e.locate(&inp))
+        .with_context(|| format!("invalid workflows in {}", display_path(&args.input)))?;
+
+    if args.lint {
+        return lint_workflows(&data, format);
+    }
+
+    let regions = data
+        .accepted_regions()
+        .map_err(anyhow::Error::msg)
+        .context("can't find the accepted parts")?;
+
+    if args.check.is_empty() && !args.parts {
+        match format {
+            Format::Text => print!("{}", regions),
+            Format::Json => {
+                let json_regions = regions
+                    .regions
+                    .iter()
+                    .map(|region| JsonRegion {
+                        path: &region.path,
+                        ranges: regions
+                            .dims
+                            .iter()
+                            .zip(&region.ranges)
+                            .map(|(&dim, r)| (dim, (r.start, r.end)))
+                            .collect(),
+                    })
+                    .collect::<Vec<_>>();
+                println!("{}", serde_json::to_string_pretty(&json_regions)?);
+            }
+        }
+        return Ok(ExitCode::SUCCESS);
+    }
+
+    let mut parts = args
+        .check
+        .iter()
+        .map(|part| {
+            data.parse_part(part)
+                .map_err(|e| e.locate(part))
+                .with_context(|| format!("invalid part {}", part))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    if args.parts {
+        parts.extend(data.parts().map(|ratings| ratings.to_vec()));
+    }
+
+    let mut json_checks = vec![];
+
+    for ratings in &parts {
+        let part = format_part(&regions.dims, ratings);
+        let region = regions.find(ratings);
+
+        // Cross-check against running the workflows, as part 1 does
+        if Some(region.is_some()) != data.accepts(ratings) {
+            bail!("accepted regions disagree with the workflows for {}", part);
+        }
+
+        match format {
+            Format::Text => match region {
+                Some(region) => println!("{}: accepted ({})", part, region.path.join(" -> ")),
+                None => println!("{}: rejected", part),
+            },
+            Format::Json => json_checks.push(JsonPartCheck {
+                part,
+                accepted: region.is_some(),
+                path: region.map(|r| r.path.as_slice()),
+            }),
+        }
+    }
+
+    if format == Format::Json {
+        println!("{}", serde_json::to_string_pretty(&json_checks)?);
+    }
+
+    Ok(ExitCode::SUCCESS)
+}
+
+#[derive(Serialize)]
+struct JsonSprings<'a> {
+    record: &'a str,
+    unfold: usize,
+    /// As a string, since it can be too large for a JSON number
+    count: String,
+    arrangements: Vec<String>,
+    /// Whether `--limit` cut the list short
+    truncated: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    seed: Option<u64>,
+}
+
+fn springs(args: &SpringsArgs, format: Format) -> Result<()> {
+    if args.unfold == 0 {
+        bail!("--unfold must be at least 1");
+    }
+
+    let mut json_records = vec![];
+
+    // Drawn once, so a single seed repeats the whole run
+    let seed = args
+        .sample
+        .map(|_| args.seed.unwrap_or_else(|| fastrand::u64(..)));
+    let mut rng = fastrand::Rng::with_seed(seed.unwrap_or_default());
+
+    for record in &args.records {
+        let data = record
+            .parse::<day12::Data>()
+            .map_err(|e| e.locate(record))
+            .with_context(|| format!("invalid record '{}'", record))?
+            .unfold(args.unfold, b'?');
+
+        // One table per record, for both the count and every sample
+        let mut arrangements = day12::Arrangements::new(&data);
+        let count = arrangements.total().clone();
+
+        let (listed, truncated) = match args.sample {
+            // Sampling stops straight away if there are no arrangements
+            Some(samples) => (
+                (0..samples)
+                    .map_while(|_| arrangements.sample(&mut rng))
+                    .collect(),
+                false,
+            ),
+            None => {
+                let listed = arrangements.by_ref().take(args.limit).collect::<Vec<_>>();
+                let truncated = arrangements.next().is_some();
+                (listed, truncated)
+            }
+        };
+
+        match format {
+            Format::Text => {
+                let unfolded = if args.unfold == 1 {
+                    String::new()
+                } else {
+                    format!(" (unfolded x{})", args.unfold)
+                };
+                let plural = if count.is_one() { "" } else { "s" };
+                println!("{}{}: {} arrangement{}", record, unfolded, count, plural);
+                if let Some(seed) = seed {
+                    println!("  random sample, seed {}:", seed);
+                }
+                for springs in listed.iter() {
+                    println!("  {}", springs);
+                }
+                if truncated {
+                    println!("  ... and {} more", count - listed.len());
+                }
+            }
+            Format::Json => json_records.push(JsonSprings {
+                record,
+                unfold: args.unfold,
+                count: count.to_string(),
+                arrangements: listed,
+                truncated,
+                seed,
+            }),
+        }
+    }
+
+    if format == Format::Json {
+        println!("{}", serde_json::to_string_pretty(&json_records)?);
+    }
+
+    Ok(())
+}
+
+#[derive(Serialize)]
+struct JsonNonogram<'a> {
+    rows: &'a [Vec<usize>],
+    columns: &'a [Vec<usize>],
+}
+
+fn solve_nonogram(args: &NonogramArgs, format: Format) -> Result<ExitCode> {
+    let inp = read_input(&args.puzzle)?;
+
+    if args.picture {
+        let picture = nonogram::parse_picture(&inp)
+            .map_err(|e| e.locate(&inp))
+            .with_context(|| format!("invalid picture in {}", display_path(&args.puzzle)))?;
+        let puzzle = Nonogram::from_picture(&picture);
+
+        match format {
+            Format::Text => print!("{}", puzzle),
+            Format::Json => println!(
+                "{}",
+                serde_json::to_string_pretty(&JsonNonogram {
+                    rows: puzzle.row_clues(),
+                    columns: puzzle.column_clues(),
+                })?
+            ),
+        }
+        return Ok(ExitCode::SUCCESS);
+    }
+
+    let puzzle = inp
+        .parse::<Nonogram>()
+        .map_err(|e| e.locate(&inp))
+        .with_context(|| format!("invalid nonogram in {}", display_path(&args.puzzle)))?;
+
+    let solutions = puzzle
+        .solutions(args.solutions)
+        .iter()
+        .map(nonogram::render_picture)
+        .collect::<Vec<_>>();
+
+    match format {
+        Format::Text => {
+            for (idx, solution) in solutions.iter().enumerate() {
+                if idx > 0 {
+                    println!();
+                }
+                print!("{}", solution);
+            }
+        }
+        Format::Json => {
+            let rows = solutions
+                .iter()
+                .map(|solution| solution.lines().collect::<Vec<_>>())
+                .collect::<Vec<_>>();
+            println!("{}", serde_json::to_string_pretty(&rows)?);
+        }
+    }
+
+    if solutions.is_empty() {
+        eprintln!("{} has no solution", display_path(&args.puzzle));
+        return Ok(ExitCode::FAILURE);
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
+#[derive(Serialize)]
+struct JsonRoute {
+    heat_loss: usize,
+    /// Each block entered, as `[row, column]`
+    route: Vec<(usize, usize)>,
+}
+
+fn crucible(args: &CrucibleArgs, format: Format) -> Result<ExitCode> {
+    let inp = read_input(&args.input)?;
+    let data = day17::parse(&inp)
+        .map_err(|e| e.locate(&inp))
+        .with_context(|| format!("invalid map in {}", display_path(&args.input)))?;
+
+    let rules = day17::Rules {
+        min_run: args.min_run,
+        max_run: args.max_run,
+        reverse: args.reverse,
+        diagonals: args.diagonals,
+        start: args.start,
+        goal: args.goal,
+    };
+    if let Err(e) = rules.validate(&data) {
+        bail!(e);
+    }
+
+    let Some((heat_loss, route)) = day17::best_route(&data, &rules) else {
+        eprintln!("no route from the start to the goal follows these rules");
+        return Ok(ExitCode::FAILURE);
+    };
+    if let Err(e) = day17::verify_route(&data, &rules, &route) {
+        bail!("route found breaks the rules: {}", e);
+    }
+
+    match format {
+        Format::Text => println!("heat loss {} over {} moves", heat_loss, route.moves.len()),
+        Format::Json => println!(
+            "{}",
+            serde_json::to_string_pretty(&JsonRoute {
+                heat_loss,
+                route: route.moves.iter().map(|&(pos, _)| pos.into()).collect(),
+            })?
+        ),
+    }
+
+    if let Some(path) = &args.render {
+        let rendered = day17::render_route(&data, &route, RenderFormat::from_path(path));
+        fs::write(path, rendered).with_context(|| format!("can't write {}", path))?;
+    }
+
+    Ok(ExitCode::SUCCESS)
+}
+
+#[derive(Serialize)]
+struct JsonPressStats {
+    low: usize,
+    high: usize,
+    flips: usize,
+}
+
+impl From<day20::PressStats> for JsonPressStats {
+    fn from(stats: day20::PressStats) -> JsonPressStats {
+        JsonPressStats {
+            low: stats.low,
+            high: stats.high,
+            flips: stats.flips,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct JsonPulses {
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    per_press: Vec<JsonPressStats>,
+    total: JsonPressStats,
+}
+
+#[derive(Serialize)]
+struct JsonCounter<'a> {
+    bits: Vec<&'a str>,
+    checker: &'a str,
+    pattern: String,
+    period: usize,
+}
+
+#[derive(Serialize)]
+struct JsonAnalysis<'a> {
+    counters: Vec<JsonCounter<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    shape_error: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    simulated_presses: Option<usize>,
+    first_low_pulse: Option<usize>,
+}
+
+fn analyse_pulses(network: &day20::Network, args: &PulsesArgs, format: Format) -> Result<()> {
+    let (counters, shape_error) = match day20::analyse(network) {
+        Ok(counters) => (counters, None),
+        Err(e) => (vec![], Some(e)),
+    };
+    let names = |ids: &[day20::ModuleId]| ids.iter().map(|&id| network.name(id)).collect();
+
+    let simulation = match &shape_error {
+        None => None,
+        Some(_) => {
+            let Some(rx) = network.id(day20::END_MODULE) else {
+                bail!("there's no {} module", day20::END_MODULE);
+            };
+            Some(day20::first_low_pulse(network, rx, args.presses))
+        }
+    };
+    let (first_low, simulated) = match simulation {
+        None => (Some(day20::combined_period(&counters)), None),
+        Some(day20::FirstLow::Press(presses)) => (Some(presses), Some(presses)),
+        Some(day20::FirstLow::Never(presses) | day20::FirstLow::GaveUp(presses)) => {
+            (None, Some(presses))
+        }
+    };
+
+    match format {
+        Format::Text => {
+            for (idx, counter) in counters.iter().enumerate() {
+                let bits: Vec<&str> = names(&counter.bits);
+                println!(
+                    "counter {}: bits {}, checker {}, pattern {}, period {}",
+                    idx + 1,
+                    bits.join(" "),
+                    network.name(counter.checker),
+                    counter.pattern(),
+                    counter.period
+                );
+            }
+            if let Some(e) = &shape_error {
+                println!("no shortcut: {}", e);
+            }
+
+            let end = day20::END_MODULE;
+            match (first_low, simulation) {
+                (Some(press), None) => println!(
+                    "{} first gets a low pulse on press {} (lcm of the counter periods)",
+                    end, press
+                ),
+                (Some(press), Some(_)) => println!(
+                    "{} first gets a low pulse on press {} (simulated)",
+                    end, press
+                ),
+                (None, Some(day20::FirstLow::Never(presses))) => println!(
+                    "{} never gets a low pulse: the network repeats a state after {} presses",
+                    end, presses
+                ),
+                (None, _) => println!(
+                    "{} hasn't got a low pulse after {} presses",
+                    end, args.presses
+                ),
+            }
+        }
+        Format::Json => println!(
+            "{}",
+            serde_json::to_string_pretty(&JsonAnalysis {
+                counters: counters
+                    .iter()
+                    .map(|counter| JsonCounter {
+                        bits: names(&counter.bits),
+                        checker: network.name(counter.checker),
+                        pattern: counter.pattern(),
+                        period: counter.period,
+                    })
+                    .collect(),
+                shape_error: shape_error.map(|e| e.to_string()),
+                simulated_presses: simulated,
+                first_low_pulse: first_low,
+            })?
+        ),
+    }
+
+    Ok(())
+}
+
+fn pulses(args: &PulsesArgs, format: Format) -> Result<()> {
+    let inp = read_input(&args.input)?;
+    let network = day20::parse(&inp)
+        .map_err(|e| e.locate(&inp))
+        .with_context(|| format!("invalid module network in {}", display_path(&args.input)))?;
+
+    if args.analyse {
+        return analyse_pulses(&network, args, format);
+    }
+
+    let mut trace: Option<Box<dyn Write>> = match args.trace.as_deref() {
+        None => None,
+        Some(STDOUT_PATH) => Some(Box::new(io::stdout().lock())),
+        Some(path) => Some(Box::new(BufWriter::new(
+            fs::File::create(path).with_context(|| format!("can't write {}", path))?,
+        ))),
+    };
+
+    let mut simulator = day20::Simulator::new(&network);
+    let mut total = day20::PressStats::default();
+    let mut json_presses = vec![];
+
+    for _ in 0..args.presses {
+        let stats = match trace.as_mut() {
+            Some(out) => {
+                // Keep the first write error, rather than stopping mid-press
+                let mut result = Ok(());
+                let stats = simulator.press_traced(&mut |event: &day20::PulseEvent| {
+                    if result.is_ok() {
+                        result = writeln!(
+                            out,
+                            "{} {}",
+                            event.press,
+                            day20::format_pulse(&network, event)
+                        );
+                    }
+                });
+                result.context("can't write trace")?;
+                stats
+            }
+            None => simulator.press(),
+        };
+        total += stats;
+
+        if args.per_press {
+            match format {
+                Format::Text => println!(
+                    "press {}: {} low, {} high, {} flips",
+                    simulator.presses(),
+                    stats.low,
+                    stats.high,
+                    stats.flips
+                ),
+                Format::Json => json_presses.push(JsonPressStats::from(stats)),
+            }
+        }
+    }
+
+    if let Some(out) = trace.as_mut() {
+        out.flush().context("can't write trace")?;
+    }
+
+    match format {
+        Format::Text => println!(
+            "{} presses: {} low, {} high (product {}), {} flips",
+            args.presses,
+            total.low,
+            total.high,
+            total.low * total.high,
+            total.flips
+        ),
+        Format::Json => println!(
+            "{}",
+            serde_json::to_string_pretty(&JsonPulses {
+                per_press: json_presses,
+                total: total.into(),
+            })?
+        ),
+    }
+
+    Ok(())
+}
+
+fn graph(args: &GraphArgs, format: Format) -> Result<()> {
+    if format != Format::Text {
+        bail!("graphs are only written as DOT, so --format json isn't supported");
+    }
+
+    let path = args
+        .input
+        .clone()
+        .unwrap_or_else(|| format!("inputs/real/2023_{:02}", args.day.day()));
+    let inp = read_input(&path)?;
+    let invalid = || {
+        format!(
+            "invalid day {} input in {}",
+            args.day.day(),
+            display_path(&path)
+        )
+    };
+
+    let dot = match args.day {
+        GraphDay::Modules => {
+            let network = day20::parse(&inp)
+                .map_err(|e| e.locate(&inp))
+                .with_context(invalid)?;
+            let counters = if args.highlight {
+                day20::analyse(&network).unwrap_or_else(|e| {
+                    eprintln!("no counters to highlight: {}", e);
+                    vec![]
+                })
+            } else {
+                vec![]
+            };
+            day20::to_dot(&network, &counters)
+        }
+        GraphDay::Trails => {
+            let data = day23::parse(&inp)
+                .map_err(|e| e.locate(&inp))
+                .with_context(invalid)?;
+            day23::to_dot(&data, args.part, args.highlight)
+        }
+        GraphDay::Wiring => {
+            let data = day25::parse(&inp)
+                .map_err(|e| e.locate(&inp))
+                .with_context(invalid)?;
+            let cut = if args.highlight {
+                Some(day25::find_cut(&data).context("can't find 3 wires to cut")?)
+            } else {
+                None
+            };
+            day25::to_dot(&data, cut.as_ref())
+        }
+    };
+
+    match &args.output {
+        Some(out) => {
+            fs::write(out, dot.to_string()).with_context(|| format!("can't write {}", out))
+        }
+        None => {
+            print!("{}", dot);
+            Ok(())
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    let args = Cli::parse();
+
+    let result = match &args.command {
+        Command::Run(run_args) => run(run_args, args.format).map(|_| ExitCode::SUCCESS),
+        Command::Verify(verify_args) => verify(verify_args, args.format),
+        Command::Almanac(almanac_args) => {
+            almanac(almanac_args, args.format).map(|_| ExitCode::SUCCESS)
+        }
+        Command::Workflows(workflows_args) => workflows(workflows_args, args.format),
+        Command::Pulses(pulses_args) => pulses(pulses_args, args.format).map(|_| ExitCode::SUCCESS),
+        Command::Graph(graph_args) => graph(graph_args, args.format).map(|_| ExitCode::SUCCESS),
+        Command::Crucible(crucible_args) => crucible(crucible_args, args.format),
+        Command::Nonogram(nonogram_args) => solve_nonogram(nonogram_args, args.format),
+        Command::Springs(springs_args) => {
+            springs(springs_args, args.format).map(|_| ExitCode::SUCCESS)
+        }
+    };
+
+    result.unwrap_or_else(|e| {
+        eprintln!("error: {:#}", e);
+        ExitCode::FAILURE
+    })
+}
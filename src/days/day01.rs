@@ -1,6 +1,5 @@
-#![cfg_attr(feature = "bench", feature(test))]
-use advent_of_code_2023::{Cli, Parser};
-use std::fs;
+use crate::parse_util::ParseError;
+use crate::Solution;
 
 fn map_to_digit<const PART: u8>(line: &str) -> Option<u32> {
     if let Some(result) = line.chars().next().and_then(|c| c.to_digit(10)) {
@@ -30,20 +29,31 @@ fn map_to_digit<const PART: u8>(line: &str) -> Option<u32> {
 }
 
 fn get_first_digit<const PART: u8>(line: &str) -> u32 {
-    (0..line.len())
-        .map(|skip| &line[skip..])
+    line.char_indices()
+        .map(|(skip, _)| &line[skip..])
         .find_map(map_to_digit::<PART>)
         .expect("no matching first digit")
 }
 
 fn get_last_digit<const PART: u8>(line: &str) -> u32 {
-    (0..line.len())
+    line.char_indices()
         .rev()
-        .map(|skip| &line[skip..])
+        .map(|(skip, _)| &line[skip..])
         .find_map(map_to_digit::<PART>)
         .expect("no matching last digit")
 }
 
+// Every line needs a digit for part 1, which also gives part 2 a first and last digit
+fn parse(raw_inp: &str) -> Result<&str, ParseError> {
+    match raw_inp
+        .lines()
+        .find(|line| !line.bytes().any(|b| b.is_ascii_digit()))
+    {
+        Some(line) => Err(ParseError::new(line, "a line containing a digit")),
+        None => Ok(raw_inp),
+    }
+}
+
 fn calculate<const PART: u8>(raw_inp: &str) -> u32 {
     raw_inp
         .lines()
@@ -56,59 +66,50 @@ fn calculate<const PART: u8>(raw_inp: &str) -> u32 {
         .sum()
 }
 
-fn main() {
-    let args = Cli::parse();
-
-    let inp = fs::read_to_string(args.input).expect("can't open input file");
-
-    let p1 = calculate::<1>(&inp);
-    let p2 = calculate::<2>(&inp);
-    println!("{}\n{}", p1, p2);
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    const EXAMPLE_DATA_P1: &str = include_str!("../../inputs/examples/2023_01_p1");
-    const EXAMPLE_DATA_P2: &str = include_str!("../../inputs/examples/2023_01_p2");
-    const REAL_DATA: &str = include_str!("../../inputs/real/2023_01");
+pub struct Day01;
 
-    #[test]
-    fn test_p1_example() {
-        assert_eq!(calculate::<1>(&EXAMPLE_DATA_P1), 142);
-    }
+impl Solution for Day01 {
+    type Input<'a> = &'a str;
+    type Answer1 = u32;
+    type Answer2 = u32;
 
-    #[test]
-    fn test_p1_real() {
-        assert_eq!(calculate::<1>(&REAL_DATA), 56506);
+    fn parse<'a>(&self, raw_inp: &'a str) -> Result<Self::Input<'a>, ParseError> {
+        parse(raw_inp)
     }
 
-    #[test]
-    fn test_p2_example() {
-        assert_eq!(calculate::<2>(&EXAMPLE_DATA_P2), 281);
+    fn part1(&self, input: &Self::Input<'_>) -> u32 {
+        calculate::<1>(input)
     }
 
-    #[test]
-    fn test_p2_real() {
-        assert_eq!(calculate::<2>(&REAL_DATA), 56017);
+    fn part2(&self, input: &Self::Input<'_>) -> Option<u32> {
+        Some(calculate::<2>(input))
     }
+}
 
-    #[cfg(feature = "bench")]
-    mod benches {
-        extern crate test;
-        use test::{black_box, Bencher};
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-        use super::*;
+    aoc_tests! {
+        real_input: "2023_01";
 
-        #[bench]
-        fn bench_p1(b: &mut Bencher) {
-            b.iter(|| calculate::<1>(black_box(REAL_DATA)));
+        p1 (part 1): calculate::<1> => {
+            example("2023_01_p1") => 142,
         }
 
-        #[bench]
-        fn bench_p2(b: &mut Bencher) {
-            b.iter(|| calculate::<2>(black_box(REAL_DATA)));
+        p2 (part 2): calculate::<2> => {
+            example("2023_01_p2") => 281,
         }
     }
+
+    #[test]
+    fn test_parse_errors() {
+        let inp = "1abc2\neightwothree\n";
+        let err = parse(inp).unwrap_err().locate(inp);
+        assert_eq!(err.location().map(|loc| loc.line), Some(2));
+
+        // Other characters around the digits are skipped
+        let data = parse("é1ñ2\n").unwrap();
+        assert_eq!(calculate::<1>(data), 12);
+    }
 }
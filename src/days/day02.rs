@@ -0,0 +1,134 @@
+use crate::parse_util::{parse_number, split_once, ParseError};
+use crate::Solution;
+use std::str::FromStr;
+
+struct SubGame {
+    red: i64,
+    green: i64,
+    blue: i64,
+}
+
+impl FromStr for SubGame {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, ParseError> {
+        let mut red = 0;
+        let mut green = 0;
+        let mut blue = 0;
+
+        for item in s.split(", ") {
+            let (n, c) = split_once(item, " ")?;
+            let n: i64 = parse_number(n)?;
+
+            match c {
+                "red" => red = n,
+                "green" => green = n,
+                "blue" => blue = n,
+                _ => return Err(ParseError::new(c, "a colour (red, green or blue)")),
+            }
+        }
+
+        Ok(SubGame { red, green, blue })
+    }
+}
+
+pub struct Game {
+    id: i64,
+    subgames: Vec<SubGame>,
+}
+
+impl FromStr for Game {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, ParseError> {
+        let (identifier, subgames) = split_once(s, ": ")?;
+
+        let id: i64 = parse_number(split_once(identifier, " ")?.1)?;
+
+        let subgames = subgames
+            .split("; ")
+            .map(|s| s.parse())
+            .collect::<Result<Vec<SubGame>, _>>()?;
+
+        Ok(Game { id, subgames })
+    }
+}
+
+fn parse(raw_inp: &str) -> Result<Vec<Game>, ParseError> {
+    raw_inp.trim().lines().map(|s| s.parse()).collect()
+}
+
+fn calculate_p1(data: &[Game]) -> i64 {
+    data.iter()
+        .filter(|game| {
+            game.subgames
+                .iter()
+                .all(|subgame| subgame.red <= 12 && subgame.green <= 13 && subgame.blue <= 14)
+        })
+        .map(|game| game.id)
+        .sum()
+}
+
+fn calculate_p2(data: &[Game]) -> i64 {
+    data.iter()
+        .map(|game| {
+            let min_red = game
+                .subgames
+                .iter()
+                .map(|subgame| subgame.red)
+                .max()
+                .expect("no red");
+            let min_green = game
+                .subgames
+                .iter()
+                .map(|subgame| subgame.green)
+                .max()
+                .expect("no green");
+            let min_blue = game
+                .subgames
+                .iter()
+                .map(|subgame| subgame.blue)
+                .max()
+                .expect("no blue");
+
+            min_red * min_green * min_blue
+        })
+        .sum()
+}
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    type Input<'a> = Vec<Game>;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse<'a>(&self, raw_inp: &'a str) -> Result<Self::Input<'a>, ParseError> {
+        parse(raw_inp)
+    }
+
+    fn part1(&self, input: &Self::Input<'_>) -> i64 {
+        calculate_p1(input)
+    }
+
+    fn part2(&self, input: &Self::Input<'_>) -> Option<i64> {
+        Some(calculate_p2(input))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    aoc_tests! {
+        real_input: "2023_02";
+
+        p1 (part 1): |inp| calculate_p1(&parse(inp).unwrap()) => {
+            example("2023_02") => 8,
+        }
+
+        p2 (part 2): |inp| calculate_p2(&parse(inp).unwrap()) => {
+            example("2023_02") => 2286,
+        }
+    }
+}
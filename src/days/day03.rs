@@ -0,0 +1,168 @@
+use crate::grid_util::{Dir, Grid, Pos};
+use crate::parse_util::ParseError;
+use crate::Solution;
+
+fn parse(raw_inp: &str) -> Result<Grid<u8>, ParseError> {
+    Grid::parse_tiles(raw_inp, "0123456789.#$%&*+-/=@")
+}
+
+const SYMBOLS: [u8; 10] = [b'#', b'$', b'%', b'&', b'*', b'+', b'-', b'/', b'=', b'@'];
+
+fn is_near_any_symbol(data: &Grid<u8>, pos: Pos) -> bool {
+    data.neighbours8(pos).any(|n| SYMBOLS.contains(&data[n]))
+}
+
+fn is_digit(data: &Grid<u8>, pos: Pos) -> bool {
+    data.get(pos).map(|x| x.is_ascii_digit()).unwrap_or(false)
+}
+
+fn is_first_digit(data: &Grid<u8>, pos: Pos) -> bool {
+    is_digit(data, pos) && !is_digit(data, pos.wrapping_step(Dir::Left))
+}
+
+fn make_number(data: &Grid<u8>, pos: Pos) -> u32 {
+    data.get(pos)
+        .and_then(|&x| char::from(x).to_digit(10))
+        .expect("make_number called on non-digit")
+}
+
+fn make_full_number(data: &Grid<u8>, mut pos: Pos) -> u32 {
+    // Scan backwards until start of number
+    while is_digit(data, pos.wrapping_step(Dir::Left)) {
+        pos = pos.wrapping_step(Dir::Left);
+    }
+
+    let mut result = make_number(data, pos);
+
+    // Scan forwards until end of number
+    while is_digit(data, pos.wrapping_step(Dir::Right)) {
+        pos = pos.wrapping_step(Dir::Right);
+        result *= 10;
+        result += make_number(data, pos);
+    }
+
+    result
+}
+
+fn calculate_p1(data: &Grid<u8>) -> u32 {
+    data.iter()
+        .filter(|&(pos, _)| is_first_digit(data, pos))
+        .filter(|&(pos, _)| {
+            let mut near = is_near_any_symbol(data, pos);
+            let mut pos = pos;
+
+            while !near && is_digit(data, pos.wrapping_step(Dir::Right)) {
+                pos = pos.wrapping_step(Dir::Right);
+                near |= is_near_any_symbol(data, pos);
+            }
+
+            near
+        })
+        .map(|(pos, _)| make_full_number(data, pos))
+        .sum()
+}
+
+fn numbers_adjacent_to(data: &Grid<u8>, pos: Pos) -> Vec<u32> {
+    // Neighbours in the left column are the only ones which can be in the
+    // middle of a number not already seen further left.
+    data.neighbours8(pos)
+        .filter(|&n| is_digit(data, n) && (n.x < pos.x || is_first_digit(data, n)))
+        .map(|n| make_full_number(data, n))
+        .collect()
+}
+
+fn calculate_p2(data: &Grid<u8>) -> u32 {
+    data.positions_of(&b'*')
+        .map(|pos| numbers_adjacent_to(data, pos))
+        .filter(|numbers| numbers.len() == 2)
+        .map(|numbers| numbers.iter().product::<u32>())
+        .sum()
+}
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    type Input<'a> = Grid<u8>;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse<'a>(&self, raw_inp: &'a str) -> Result<Self::Input<'a>, ParseError> {
+        parse(raw_inp)
+    }
+
+    fn part1(&self, input: &Self::Input<'_>) -> u32 {
+        calculate_p1(input)
+    }
+
+    fn part2(&self, input: &Self::Input<'_>) -> Option<u32> {
+        Some(calculate_p2(input))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    aoc_tests! {
+        real_input: "2023_03";
+
+        p1 (part 1): |inp| calculate_p1(&parse(inp).unwrap()) => {
+            example("2023_03") => 4361,
+        }
+
+        p2 (part 2): |inp| calculate_p2(&parse(inp).unwrap()) => {
+            example("2023_03") => 467835,
+        }
+    }
+
+    const TEST_DATA_1: &str = "
+.......5......
+..7*..*.......
+...*13*.......
+.......15.....";
+
+    const TEST_DATA_2: &str = "
+12.......*..
++.........34
+.......-12..
+..78........
+..*....60...
+78.........9
+.5.....23..$
+8...90*12...
+............
+2.2......12.
+.*.........*
+1.1..503+.56";
+
+    const TEST_DATA_3: &str = "
+333.3
+...*.";
+
+    const TEST_DATA_4: &str = "
+....................
+..-52..52-..52..52..
+..................-.";
+
+    #[test]
+    fn test_test_data_1() {
+        assert_eq!(calculate_p2(&parse(TEST_DATA_1).unwrap()), 442);
+    }
+
+    #[test]
+    fn test_test_data_2() {
+        assert_eq!(calculate_p1(&parse(TEST_DATA_2).unwrap()), 925);
+        assert_eq!(calculate_p2(&parse(TEST_DATA_2).unwrap()), 6756);
+    }
+
+    #[test]
+    fn test_test_data_3() {
+        assert_eq!(calculate_p1(&parse(TEST_DATA_3).unwrap()), 336);
+        assert_eq!(calculate_p2(&parse(TEST_DATA_3).unwrap()), 999);
+    }
+
+    #[test]
+    fn test_test_data_4() {
+        assert_eq!(calculate_p1(&parse(TEST_DATA_4).unwrap()), 156);
+    }
+}
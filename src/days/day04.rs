@@ -0,0 +1,105 @@
+use crate::parse_util::{parse_number, split_once, ParseError};
+use crate::Solution;
+use std::str::FromStr;
+
+struct Game {
+    winning_cards: Vec<u32>,
+    cards: Vec<u32>,
+}
+
+fn parse_number_list(s: &str) -> Result<Vec<u32>, ParseError> {
+    s.split_whitespace().map(parse_number).collect()
+}
+
+impl FromStr for Game {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, ParseError> {
+        let (_, numbers) = split_once(s, ": ")?;
+
+        let (winning_cards, cards) = split_once(numbers, "|")?;
+
+        let winning_cards = parse_number_list(winning_cards)?;
+        let cards = parse_number_list(cards)?;
+
+        Ok(Game {
+            winning_cards,
+            cards,
+        })
+    }
+}
+
+fn get_num_wins(game: Game) -> u32 {
+    game.cards
+        .into_iter()
+        .filter(|x| game.winning_cards.contains(x))
+        .count()
+        .try_into()
+        .expect("exceeded u32 range")
+}
+
+fn parse(raw_inp: &str) -> Result<Vec<u32>, ParseError> {
+    raw_inp
+        .trim()
+        .lines()
+        .map(|line| line.parse().map(get_num_wins))
+        .collect()
+}
+
+fn calculate_p1(data: &[u32]) -> u32 {
+    data.iter()
+        .filter(|&&wins| wins >= 1)
+        .map(|wins| 2u32.pow(wins - 1))
+        .sum()
+}
+
+fn calculate_p2(data: &[u32]) -> u32 {
+    let mut n_cards = vec![1; data.len()];
+
+    for (idx, num_wins) in data.iter().enumerate() {
+        for x in 1..=*num_wins as usize {
+            // precondition in puzzle description: will not get
+            // out-of-bounds index here.
+            n_cards[idx + x] += n_cards[idx];
+        }
+    }
+
+    n_cards.iter().sum()
+}
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    type Input<'a> = Vec<u32>;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse<'a>(&self, raw_inp: &'a str) -> Result<Self::Input<'a>, ParseError> {
+        parse(raw_inp)
+    }
+
+    fn part1(&self, input: &Self::Input<'_>) -> u32 {
+        calculate_p1(input)
+    }
+
+    fn part2(&self, input: &Self::Input<'_>) -> Option<u32> {
+        Some(calculate_p2(input))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    aoc_tests! {
+        real_input: "2023_04";
+
+        p1 (part 1): |inp| calculate_p1(&parse(inp).unwrap()) => {
+            example("2023_04") => 13,
+        }
+
+        p2 (part 2): |inp| calculate_p2(&parse(inp).unwrap()) => {
+            example("2023_04") => 30,
+        }
+    }
+}
@@ -0,0 +1,462 @@
+use crate::interval_set::IntervalSet;
+use crate::parse_util::{parse_number, split_once, strip_prefix, strip_suffix, ParseError};
+use crate::Solution;
+use itertools::*;
+use std::fmt;
+use std::ops::Range;
+use std::str::FromStr;
+
+#[derive(Debug)]
+struct Mapping {
+    dest: i64,
+    src: i64,
+    range: i64,
+}
+
+#[derive(Debug)]
+struct SeedMap {
+    // Categories mapped between, e.g. "seed" to "soil"
+    from: String,
+    to: String,
+    mapping: Vec<Mapping>,
+}
+
+impl Mapping {
+    fn src_range(&self) -> Range<i64> {
+        self.src..self.src + self.range
+    }
+}
+
+impl SeedMap {
+    // Mappings are tried in order, so a value covered by several mappings uses the first
+    fn apply(&self, seeds: &IntervalSet<i64>) -> IntervalSet<i64> {
+        let mut unmapped = seeds.clone();
+        let mut result = IntervalSet::new();
+
+        for m in self.mapping.iter() {
+            let src = IntervalSet::from(m.src_range());
+
+            result = result.union(&unmapped.intersection(&src).shift(m.dest - m.src));
+            unmapped = unmapped.difference(&src);
+        }
+
+        // Anything not covered by a mapping maps to itself
+        result.union(&unmapped)
+    }
+
+    // Every input which maps to one of `outputs`
+    fn inverse(&self, outputs: &IntervalSet<i64>) -> IntervalSet<i64> {
+        PiecewiseMap::from(self).preimage(outputs)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+struct Segment {
+    start: i64,
+    offset: i64,
+}
+
+/// A map made of sorted segments, each adding a constant offset to the values
+/// from its start up to the start of the next segment. Segments cover every
+/// non-negative number.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PiecewiseMap {
+    segments: Vec<Segment>,
+}
+
+impl PiecewiseMap {
+    fn identity() -> PiecewiseMap {
+        PiecewiseMap {
+            segments: vec![Segment {
+                start: 0,
+                offset: 0,
+            }],
+        }
+    }
+
+    // Pieces must be disjoint and between them cover every non-negative number
+    fn from_pieces(mut pieces: Vec<(Range<i64>, i64)>) -> PiecewiseMap {
+        pieces.sort_unstable_by_key(|(range, _)| range.start);
+
+        let mut segments: Vec<Segment> = Vec::with_capacity(pieces.len());
+        for (range, offset) in pieces {
+            // Neighbouring pieces with the same offset are one segment
+            if segments.last().map(|s| s.offset) != Some(offset) {
+                segments.push(Segment {
+                    start: range.start,
+                    offset,
+                });
+            }
+        }
+
+        debug_assert_eq!(segments.first().map(|s| s.start), Some(0));
+        PiecewiseMap { segments }
+    }
+
+    fn segment_end(&self, idx: usize) -> i64 {
+        self.segments.get(idx + 1).map_or(i64::MAX, |s| s.start)
+    }
+
+    /// Each segment's input range and the offset it adds, in ascending order.
+    pub fn pieces(&self) -> impl Iterator<Item = (Range<i64>, i64)> + '_ {
+        self.segments
+            .iter()
+            .enumerate()
+            .map(|(idx, s)| (s.start..self.segment_end(idx), s.offset))
+    }
+
+    /// Maps a single value, using a binary search for its segment.
+    pub fn apply(&self, value: i64) -> i64 {
+        match self.segments.partition_point(|s| s.start <= value) {
+            0 => value,
+            idx => value + self.segments[idx - 1].offset,
+        }
+    }
+
+    /// The map which applies `self` and then `next`.
+    pub fn then(&self, next: &PiecewiseMap) -> PiecewiseMap {
+        let mut pieces = vec![];
+
+        for (range, offset) in self.pieces() {
+            let image = range.start + offset..range.end.saturating_add(offset);
+
+            // Split the image wherever the next map's segments change
+            let first = next.segments.partition_point(|s| s.start <= image.start) - 1;
+            for idx in first..next.segments.len() {
+                let start = next.segments[idx].start.max(image.start);
+                let end = next.segment_end(idx).min(image.end);
+                if start >= end {
+                    break;
+                }
+                pieces.push((
+                    start - offset..end - offset,
+                    offset + next.segments[idx].offset,
+                ));
+            }
+        }
+
+        PiecewiseMap::from_pieces(pieces)
+    }
+
+    /// Every input which maps to one of `outputs`.
+    pub fn preimage(&self, outputs: &IntervalSet<i64>) -> IntervalSet<i64> {
+        self.pieces()
+            .flat_map(|(range, offset)| {
+                let image =
+                    IntervalSet::from(range.start + offset..range.end.saturating_add(offset));
+                image.intersection(outputs).shift(-offset).ranges().to_vec()
+            })
+            .collect()
+    }
+}
+
+impl From<&SeedMap> for PiecewiseMap {
+    fn from(seed_map: &SeedMap) -> PiecewiseMap {
+        let mut unmapped = IntervalSet::from(0..i64::MAX);
+        let mut pieces = vec![];
+
+        for m in seed_map.mapping.iter() {
+            let src = IntervalSet::from(m.src_range());
+            let offset = m.dest - m.src;
+
+            pieces.extend(
+                unmapped
+                    .intersection(&src)
+                    .ranges()
+                    .iter()
+                    .map(|r| (r.clone(), offset)),
+            );
+            unmapped = unmapped.difference(&src);
+        }
+
+        pieces.extend(unmapped.ranges().iter().map(|r| (r.clone(), 0)));
+
+        PiecewiseMap::from_pieces(pieces)
+    }
+}
+
+impl fmt::Display for PiecewiseMap {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "{:>12} {:>12} {:>12}", "from", "to", "offset")?;
+        for (range, offset) in self.pieces() {
+            let end = if range.end == i64::MAX {
+                "max".to_string()
+            } else {
+                range.end.to_string()
+            };
+            writeln!(f, "{:>12} {:>12} {:>+12}", range.start, end, offset)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+pub struct Data {
+    seeds: Vec<i64>,
+    maps: Vec<SeedMap>,
+    location_map: PiecewiseMap,
+}
+
+impl Data {
+    /// All of the almanac's maps composed into one, from seed to location.
+    pub fn location_map(&self) -> &PiecewiseMap {
+        &self.location_map
+    }
+
+    /// Every seed which ends up at `location`.
+    pub fn seeds_for_location(&self, location: i64) -> IntervalSet<i64> {
+        self.location_map
+            .preimage(&IntervalSet::from(location..location + 1))
+    }
+
+    /// Categories in the order the almanac maps between them, starting with "seed".
+    pub fn categories(&self) -> impl Iterator<Item = &str> {
+        self.maps
+            .first()
+            .map(|mp| mp.from.as_str())
+            .into_iter()
+            .chain(self.maps.iter().map(|mp| mp.to.as_str()))
+    }
+
+    /// Every seed which maps to one of `values` in `category`, e.g. "soil", or
+    /// `None` if the almanac has no such category.
+    pub fn seeds_for(&self, category: &str, values: &IntervalSet<i64>) -> Option<IntervalSet<i64>> {
+        let stages = self.categories().position(|c| c == category)?;
+
+        Some(
+            self.maps[..stages]
+                .iter()
+                .rev()
+                .fold(values.clone(), |values, mp| mp.inverse(&values)),
+        )
+    }
+}
+
+impl FromStr for Mapping {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, ParseError> {
+        let nums = s
+            .split_whitespace()
+            .map(parse_number)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if nums.len() != 3 {
+            return Err(ParseError::new(s, "3 numbers (dest, src, range)"));
+        }
+
+        // Maps are only defined over non-negative numbers, and ranges mustn't overflow
+        let in_bounds = |n: i64| n >= 0 && n.checked_add(nums[2]).is_some();
+        if !nums.iter().all(|&n| in_bounds(n)) {
+            return Err(ParseError::new(
+                s,
+                "3 non-negative numbers, with ranges that fit in an i64",
+            ));
+        }
+
+        Ok(Mapping {
+            dest: nums[0],
+            src: nums[1],
+            range: nums[2],
+        })
+    }
+}
+
+impl FromStr for SeedMap {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, ParseError> {
+        let header = s.lines().next().unwrap_or_default();
+        let (from, to) = split_once(strip_suffix(header, " map:")?, "-to-")?;
+
+        let mapping = s
+            .lines()
+            .skip(1) // Header
+            .map(|line| line.parse())
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(SeedMap {
+            from: from.to_string(),
+            to: to.to_string(),
+            mapping,
+        })
+    }
+}
+
+impl FromStr for Data {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, ParseError> {
+        let seeds_line = strip_prefix(s.lines().next().unwrap_or_default(), "seeds:")?;
+        let mut seeds = vec![];
+
+        // Part 2 reads the seeds as pairs of a start and a length, which must
+        // be non-negative and not overflow, like the mappings
+        for pair in &seeds_line.split_whitespace().chunks(2) {
+            let pair = pair.collect::<Vec<_>>();
+            let &[start, len] = pair.as_slice() else {
+                return Err(ParseError::new(pair[0], "a seed range (start, length)"));
+            };
+            let (start, len): (i64, i64) = (parse_number(start)?, parse_number(len)?);
+            if start < 0 || len <= 0 || start.checked_add(len).is_none() {
+                return Err(ParseError::new(
+                    pair[0],
+                    "a non-negative start and positive length, with a range that fits in an i64",
+                ));
+            }
+            seeds.extend([start, len]);
+        }
+
+        if seeds.is_empty() {
+            return Err(ParseError::new(seeds_line, "at least one seed range"));
+        }
+
+        let maps: Vec<SeedMap> = s
+            .split("\n\n")
+            .skip(1) // Seeds
+            .map(|line| line.parse())
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let location_map = maps.iter().fold(PiecewiseMap::identity(), |composed, mp| {
+            composed.then(&PiecewiseMap::from(mp))
+        });
+
+        Ok(Data {
+            seeds,
+            maps,
+            location_map,
+        })
+    }
+}
+
+fn parse(raw_inp: &str) -> Result<Data, ParseError> {
+    raw_inp.parse()
+}
+
+fn calculate_p1(data: &Data) -> i64 {
+    data.seeds
+        .iter()
+        .map(|&seed| data.location_map.apply(seed))
+        .min()
+        .expect("should have at least one seed")
+}
+
+fn calculate_p2(data: &Data) -> i64 {
+    let seeds = data
+        .seeds
+        .iter()
+        .tuples()
+        .map(|(&start, &range)| start..start + range)
+        .collect::<IntervalSet<_>>();
+
+    data.maps
+        .iter()
+        .fold(seeds, |seeds, mp| mp.apply(&seeds))
+        .min()
+        .expect("expected at least one seed range")
+}
+
+pub struct Day05;
+
+impl Solution for Day05 {
+    type Input<'a> = Data;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse<'a>(&self, raw_inp: &'a str) -> Result<Self::Input<'a>, ParseError> {
+        parse(raw_inp)
+    }
+
+    fn part1(&self, input: &Self::Input<'_>) -> i64 {
+        calculate_p1(input)
+    }
+
+    fn part2(&self, input: &Self::Input<'_>) -> Option<i64> {
+        Some(calculate_p2(input))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    aoc_tests! {
+        real_input: "2023_05";
+
+        p1 (part 1): |inp| calculate_p1(&parse(inp).unwrap()) => {
+            example("2023_05") => 35,
+        }
+
+        p2 (part 2): |inp| calculate_p2(&parse(inp).unwrap()) => {
+            example("2023_05") => 46,
+        }
+    }
+
+    #[test]
+    fn test_location_map() {
+        let data = parse(fixture!("2023_05")).unwrap();
+
+        // Composed map agrees with applying each map in turn
+        for seed in 0..120 {
+            let expected = data
+                .maps
+                .iter()
+                .fold(IntervalSet::from(seed..seed + 1), |seeds, mp| {
+                    mp.apply(&seeds)
+                });
+            assert_eq!(data.location_map().apply(seed), expected.min().unwrap());
+        }
+
+        let seeds = data.seeds_for_location(35);
+        assert_eq!(
+            data.seeds_for("location", &IntervalSet::from(35..36)),
+            Some(seeds.clone())
+        );
+        assert!(seeds.contains(13));
+        for range in seeds.ranges() {
+            for seed in range.clone() {
+                assert_eq!(data.location_map().apply(seed), 35);
+            }
+        }
+    }
+
+    #[test]
+    fn test_seeds_for() {
+        let data = parse(fixture!("2023_05")).unwrap();
+
+        assert_eq!(data.categories().next(), Some("seed"));
+        assert_eq!(data.categories().last(), Some("location"));
+
+        // Seed-to-soil maps 98..100 to 50..52 and 50..98 to 52..100
+        assert_eq!(
+            data.seeds_for("soil", &IntervalSet::from(49..53))
+                .unwrap()
+                .ranges(),
+            &[49..51, 98..100]
+        );
+        assert_eq!(
+            data.seeds_for("seed", &IntervalSet::from(3..5)),
+            Some(IntervalSet::from(3..5))
+        );
+        assert_eq!(data.seeds_for("sand", &IntervalSet::from(3..5)), None);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let almanac = |mapping: &str| format!("seeds: 1 2\n\na-to-b map:\n{}\n", mapping);
+
+        assert!(parse(&almanac("5 0 10")).is_ok());
+        assert!(parse(&almanac("-5 0 10")).is_err());
+        assert!(parse(&almanac("5 -3 10")).is_err());
+        assert!(parse(&almanac("5 0 -10")).is_err());
+        assert!(parse(&almanac(&format!("5 0 {}", i64::MAX))).is_err());
+
+        let seeds = |seeds: &str| parse(&format!("seeds:{}\n\na-to-b map:\n5 0 10\n", seeds));
+        assert!(seeds(" 0 1 79 14").is_ok());
+        assert!(seeds("").is_err());
+        assert!(seeds(" 79 14 55").is_err());
+        assert!(seeds(" -1 5").is_err());
+        assert!(seeds(" 79 0").is_err());
+        assert!(seeds(" 9223372036854775800 100").is_err());
+    }
+}
@@ -1,37 +1,39 @@
-#![cfg_attr(feature = "bench", feature(test))]
-use advent_of_code_2023::{Cli, Parser};
-use anyhow::*;
+use crate::parse_util::{parse_number, split_once, ParseError};
+use crate::Solution;
 use num_integer::*;
-use std::fs;
 
 #[derive(Clone)]
-struct Race {
+pub struct Race {
     time: i64,
     distance: i64,
 }
 
-fn parse_line(raw_inp: &str) -> Vec<i64> {
+fn parse_line(raw_inp: &str) -> Result<Vec<i64>, ParseError> {
     raw_inp
         .trim()
         .split_ascii_whitespace()
         .skip(1) // "Time:" or "Distance:"
-        .map(|x| x.parse())
-        .collect::<Result<Vec<i64>, _>>()
-        .expect("Couldn't parse line")
+        .map(parse_number)
+        .collect()
 }
 
-fn parse(raw_inp: &str) -> Vec<Race> {
-    let (times, distances) = raw_inp
-        .trim()
-        .split_once('\n')
-        .map(|(t, d)| (parse_line(t), parse_line(d)))
-        .expect("not enough lines");
+fn parse(raw_inp: &str) -> Result<Vec<Race>, ParseError> {
+    let (times_line, distances_line) = split_once(raw_inp.trim(), "\n")?;
+    let times = parse_line(times_line)?;
+    let distances = parse_line(distances_line)?;
 
-    times
+    if times.len() != distances.len() {
+        return Err(ParseError::new(
+            distances_line,
+            format!("{} distances, one per race", times.len()),
+        ));
+    }
+
+    Ok(times
         .iter()
         .zip(distances)
         .map(|(&time, distance)| Race { time, distance })
-        .collect()
+        .collect())
 }
 
 fn ways_to_win(race: &Race) -> i64 {
@@ -61,8 +63,9 @@ fn next_power_of_10(n: i64) -> i64 {
     10_i64.pow(n.ilog10() + 1)
 }
 
-fn calculate_p2(data: Vec<Race>) -> i64 {
-    data.into_iter()
+fn calculate_p2(data: &[Race]) -> i64 {
+    data.iter()
+        .cloned()
         .reduce(|acc, e| Race {
             time: next_power_of_10(e.time) * acc.time + e.time,
             distance: next_power_of_10(e.distance) * acc.distance + e.distance,
@@ -71,23 +74,41 @@ fn calculate_p2(data: Vec<Race>) -> i64 {
         .expect("expected at least one race")
 }
 
-fn main() {
-    let args = Cli::parse();
+pub struct Day06;
 
-    let inp = fs::read_to_string(args.input).expect("can't open input file");
+impl Solution for Day06 {
+    type Input<'a> = Vec<Race>;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse<'a>(&self, raw_inp: &'a str) -> Result<Self::Input<'a>, ParseError> {
+        parse(raw_inp)
+    }
+
+    fn part1(&self, input: &Self::Input<'_>) -> i64 {
+        calculate_p1(input)
+    }
 
-    let data = parse(&inp);
-    let p1 = calculate_p1(&data);
-    let p2 = calculate_p2(data);
-    println!("{}\n{}", p1, p2);
+    fn part2(&self, input: &Self::Input<'_>) -> Option<i64> {
+        Some(calculate_p2(input))
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    const EXAMPLE_DATA: &str = include_str!("../../inputs/examples/2023_06");
-    const REAL_DATA: &str = include_str!("../../inputs/real/2023_06");
+    aoc_tests! {
+        real_input: "2023_06";
+
+        p1 (part 1): |inp| calculate_p1(&parse(inp).unwrap()) => {
+            example("2023_06") => 288,
+        }
+
+        p2 (part 2): |inp| calculate_p2(&parse(inp).unwrap()) => {
+            example("2023_06") => 71503,
+        }
+    }
 
     #[test]
     fn test_simple_winnable_races() {
@@ -136,49 +157,4 @@ mod tests {
             0
         );
     }
-
-    #[test]
-    fn test_p1_example() {
-        assert_eq!(calculate_p1(&parse(EXAMPLE_DATA)), 288);
-    }
-
-    #[test]
-    fn test_p2_example() {
-        assert_eq!(calculate_p2(parse(EXAMPLE_DATA)), 71503);
-    }
-
-    #[test]
-    fn test_p1_real() {
-        assert_eq!(calculate_p1(&parse(REAL_DATA)), 170000);
-    }
-
-    #[test]
-    fn test_p2_real() {
-        assert_eq!(calculate_p2(parse(REAL_DATA)), 20537782);
-    }
-
-    #[cfg(feature = "bench")]
-    mod benches {
-        extern crate test;
-        use test::{black_box, Bencher};
-
-        use super::*;
-
-        #[bench]
-        fn bench_parse(b: &mut Bencher) {
-            b.iter(|| parse(black_box(REAL_DATA)));
-        }
-
-        #[bench]
-        fn bench_p1(b: &mut Bencher) {
-            let parsed = parse(REAL_DATA);
-            b.iter(|| calculate_p1(black_box(&parsed)));
-        }
-
-        #[bench]
-        fn bench_p2(b: &mut Bencher) {
-            let parsed = parse(REAL_DATA);
-            b.iter(|| calculate_p2(black_box(parsed.clone())));
-        }
-    }
 }
@@ -1,10 +1,9 @@
-#![cfg_attr(feature = "bench", feature(test))]
-use advent_of_code_2023::{Cli, Parser};
+use crate::parse_util::{parse_number, split_once, ParseError};
+use crate::Solution;
 use std::cmp::Ordering;
-use std::fs;
 
-#[derive(Eq, PartialEq)]
-struct Hand {
+#[derive(Eq, PartialEq, Clone)]
+pub struct Hand {
     bid: u64,
     strength_p1: HandType,
     strength_p2: HandType,
@@ -98,20 +97,32 @@ fn compare_hands<const PART: u8>(hand1: &Hand, hand2: &Hand) -> Ordering {
     }
 }
 
-fn parse_line(inp: &str) -> Hand {
-    let (cards_str, bid_str) = inp.split_once(' ').expect("invalid format");
+const CARDS: &[u8] = b"23456789TJQKA";
 
-    let cards: Vec<u8> = cards_str.bytes().collect();
+fn parse_line(inp: &str) -> Result<Hand, ParseError> {
+    let (cards_str, bid_str) = split_once(inp, " ")?;
 
-    Hand {
+    if let Some(idx) = cards_str.bytes().position(|c| !CARDS.contains(&c)) {
+        return Err(ParseError::new(
+            &cards_str[idx..],
+            "a card (one of 23456789TJQKA)",
+        ));
+    }
+
+    let cards: [u8; 5] = cards_str
+        .as_bytes()
+        .try_into()
+        .map_err(|_| ParseError::new(cards_str, "a hand of 5 cards"))?;
+
+    Ok(Hand {
         strength_p1: hand_strength::<1>(&cards),
         strength_p2: hand_strength::<2>(&cards),
-        cards: cards.try_into().expect("invalid card length"),
-        bid: bid_str.parse().expect("invalid bid"),
-    }
+        cards,
+        bid: parse_number(bid_str)?,
+    })
 }
 
-fn parse(raw_inp: &str) -> Vec<Hand> {
+fn parse(raw_inp: &str) -> Result<Vec<Hand>, ParseError> {
     raw_inp.trim().lines().map(parse_line).collect()
 }
 
@@ -121,64 +132,39 @@ fn calculate<const PART: u8>(data: &mut [Hand]) -> u64 {
     data.iter().zip(1..).map(|(hand, idx)| idx * hand.bid).sum()
 }
 
-fn main() {
-    let args = Cli::parse();
-
-    let inp = fs::read_to_string(args.input).expect("can't open input file");
+pub struct Day07;
 
-    let mut data = parse(&inp);
-    let p1 = calculate::<1>(&mut data);
-    let p2 = calculate::<2>(&mut data);
-    println!("{}\n{}", p1, p2);
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
+impl Solution for Day07 {
+    type Input<'a> = Vec<Hand>;
+    type Answer1 = u64;
+    type Answer2 = u64;
 
-    const EXAMPLE_DATA: &str = include_str!("../../inputs/examples/2023_07");
-    const REAL_DATA: &str = include_str!("../../inputs/real/2023_07");
-
-    #[test]
-    fn test_p1_example() {
-        assert_eq!(calculate::<1>(&mut parse(EXAMPLE_DATA)), 6440);
-    }
-
-    #[test]
-    fn test_p2_example() {
-        assert_eq!(calculate::<2>(&mut parse(EXAMPLE_DATA)), 5905);
+    fn parse<'a>(&self, raw_inp: &'a str) -> Result<Self::Input<'a>, ParseError> {
+        parse(raw_inp)
     }
 
-    #[test]
-    fn test_p1_real() {
-        assert_eq!(calculate::<1>(&mut parse(REAL_DATA)), 251216224);
+    fn part1(&self, input: &Self::Input<'_>) -> u64 {
+        calculate::<1>(&mut input.clone())
     }
 
-    #[test]
-    fn test_p2_real() {
-        assert_eq!(calculate::<2>(&mut parse(REAL_DATA)), 250825971);
+    fn part2(&self, input: &Self::Input<'_>) -> Option<u64> {
+        Some(calculate::<2>(&mut input.clone()))
     }
+}
 
-    #[cfg(feature = "bench")]
-    mod benches {
-        extern crate test;
-        use test::{black_box, Bencher};
-
-        use super::*;
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-        #[bench]
-        fn bench_parse(b: &mut Bencher) {
-            b.iter(|| parse(black_box(REAL_DATA)));
-        }
+    aoc_tests! {
+        real_input: "2023_07";
 
-        #[bench]
-        fn bench_p1_with_parse(b: &mut Bencher) {
-            b.iter(|| calculate::<1>(black_box(&mut parse(REAL_DATA))));
+        p1 (part 1): |inp| calculate::<1>(&mut parse(inp).unwrap()) => {
+            example("2023_07") => 6440,
         }
 
-        #[bench]
-        fn bench_p2_with_parse(b: &mut Bencher) {
-            b.iter(|| calculate::<2>(black_box(&mut parse(REAL_DATA))));
+        p2 (part 2): |inp| calculate::<2>(&mut parse(inp).unwrap()) => {
+            example("2023_07") => 5905,
         }
     }
 }
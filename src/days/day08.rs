@@ -1,9 +1,8 @@
-#![cfg_attr(feature = "bench", feature(test))]
-use advent_of_code_2023::{Cli, Parser};
+use crate::parse_util::ParseError;
+use crate::Solution;
 use num::Integer;
-use std::fs;
 
-struct Data {
+pub struct Data {
     directions: Vec<u8>,
     location_map: LocationMap,
     p2_starts: Vec<Location>,
@@ -67,40 +66,71 @@ impl LocationMap {
     }
 }
 
-fn parse(raw_inp: &[u8]) -> Data {
-    let directions = raw_inp
+fn parse_location(line: &[u8], start: usize) -> Result<Location, ParseError> {
+    line.get(start..start + 3)
+        .filter(|loc| loc.iter().all(|c| c.is_ascii_uppercase()))
+        .map(|loc| {
+            <&[u8; 3]>::try_from(loc)
+                .expect("slice has length 3")
+                .into()
+        })
+        .ok_or_else(|| {
+            ParseError::new(&line[start.min(line.len())..], "a location (3 letters A-Z)")
+        })
+}
+
+fn expect_bytes(line: &[u8], start: usize, expected: &[u8]) -> Result<(), ParseError> {
+    if line.get(start..start + expected.len()) == Some(expected) {
+        Ok(())
+    } else {
+        Err(ParseError::new(
+            &line[start.min(line.len())..],
+            format!("'{}'", String::from_utf8_lossy(expected)),
+        ))
+    }
+}
+
+fn parse(raw_inp: &[u8]) -> Result<Data, ParseError> {
+    let directions: Vec<u8> = raw_inp
         .split(|&elem| elem == b'\n')
         .next()
         .map(|line| line.into())
-        .expect("can't parse directions");
+        .unwrap_or_default();
+
+    if let Some(idx) = directions.iter().position(|&d| d != b'L' && d != b'R') {
+        return Err(ParseError::new(&raw_inp[idx..], "a direction (L or R)"));
+    } else if directions.is_empty() {
+        return Err(ParseError::new(raw_inp, "a list of directions"));
+    }
 
     let mut location_map = LocationMap::new();
 
     let mut p2_starts = vec![];
 
-    raw_inp
+    for line in raw_inp
         .split(|&elem| elem == b'\n')
         .skip(2) // Directions + blank line
         .filter(|line| !line.is_empty())
-        .for_each(|line| {
-            let src: &[u8; 3] = line[0..3].try_into().unwrap();
-            let left: &[u8; 3] = line[7..10].try_into().unwrap();
-            let right: &[u8; 3] = line[12..15].try_into().unwrap();
-
-            let src: Location = src.into();
-
-            location_map.set(src, left.into(), right.into());
-
-            if src.ends_with::<b'A'>() {
-                p2_starts.push(src);
-            }
-        });
+    {
+        let src = parse_location(line, 0)?;
+        expect_bytes(line, 3, b" = (")?;
+        let left = parse_location(line, 7)?;
+        expect_bytes(line, 10, b", ")?;
+        let right = parse_location(line, 12)?;
+        expect_bytes(line, 15, b")")?;
+
+        location_map.set(src, left, right);
+
+        if src.ends_with::<b'A'>() {
+            p2_starts.push(src);
+        }
+    }
 
-    Data {
+    Ok(Data {
         directions,
         location_map,
         p2_starts,
-    }
+    })
 }
 
 fn search(data: &Data, start: Location, cond: fn(Location) -> bool) -> u64 {
@@ -135,65 +165,38 @@ fn calculate_p2(data: &Data) -> u64 {
         .fold(1, |acc, elem| acc.lcm(&elem))
 }
 
-fn main() {
-    let args = Cli::parse();
-
-    let inp = fs::read(args.input).expect("can't open input file");
+pub struct Day08;
 
-    let data = parse(&inp);
-    let p1 = calculate_p1(&data);
-    let p2 = calculate_p2(&data);
-    println!("{}\n{}", p1, p2);
-}
+impl Solution for Day08 {
+    type Input<'a> = Data;
+    type Answer1 = u64;
+    type Answer2 = u64;
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    const EXAMPLE_DATA_P1_1: &[u8] = include_bytes!("../../inputs/examples/2023_08_p1_ex1");
-    const EXAMPLE_DATA_P1_2: &[u8] = include_bytes!("../../inputs/examples/2023_08_p1_ex2");
-    const REAL_DATA: &[u8] = include_bytes!("../../inputs/real/2023_08");
-
-    #[test]
-    fn test_p1_example_1() {
-        assert_eq!(calculate_p1(&mut parse(EXAMPLE_DATA_P1_1)), 2);
-    }
-
-    #[test]
-    fn test_p1_example_2() {
-        assert_eq!(calculate_p1(&mut parse(EXAMPLE_DATA_P1_2)), 6);
+    fn parse<'a>(&self, raw_inp: &'a str) -> Result<Self::Input<'a>, ParseError> {
+        parse(raw_inp.as_bytes())
     }
 
-    #[test]
-    fn test_p1_real() {
-        assert_eq!(calculate_p1(&mut parse(REAL_DATA)), 12169);
+    fn part1(&self, input: &Self::Input<'_>) -> u64 {
+        calculate_p1(input)
     }
 
-    #[test]
-    fn test_p2_real() {
-        assert_eq!(calculate_p2(&mut parse(REAL_DATA)), 12030780859469);
+    fn part2(&self, input: &Self::Input<'_>) -> Option<u64> {
+        Some(calculate_p2(input))
     }
+}
 
-    #[cfg(feature = "bench")]
-    mod benches {
-        extern crate test;
-        use test::{black_box, Bencher};
-
-        use super::*;
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-        #[bench]
-        fn bench_parse(b: &mut Bencher) {
-            b.iter(|| parse(black_box(REAL_DATA)));
-        }
+    aoc_tests! {
+        real_input: "2023_08";
 
-        #[bench]
-        fn bench_p1_with_parse(b: &mut Bencher) {
-            b.iter(|| calculate_p1(black_box(&mut parse(REAL_DATA))));
+        p1 (part 1): |inp| calculate_p1(&parse(inp.as_bytes()).unwrap()) => {
+            example("2023_08_p1_ex1") => 2,
+            example_2("2023_08_p1_ex2") => 6,
         }
 
-        #[bench]
-        fn bench_p2_with_parse(b: &mut Bencher) {
-            b.iter(|| calculate_p2(black_box(&mut parse(REAL_DATA))));
-        }
+        p2 (part 2): |inp| calculate_p2(&parse(inp.as_bytes()).unwrap()) => {}
     }
 }
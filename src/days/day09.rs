@@ -0,0 +1,95 @@
+use crate::parse_util::{parse_number, ParseError};
+use crate::Solution;
+use itertools::Itertools;
+
+fn parse(raw_inp: &str) -> Result<Vec<Vec<i64>>, ParseError> {
+    raw_inp
+        .trim()
+        .lines()
+        .map(|line| {
+            if line.trim().is_empty() {
+                return Err(ParseError::new(line, "a sequence of numbers"));
+            }
+            line.split_ascii_whitespace().map(parse_number).collect()
+        })
+        .collect()
+}
+
+fn extrapolate(mut nums: Vec<i64>) -> (i64, i64) {
+    // Use a buffer-swapping implementation so we never have more
+    // than 2 arrays (current + next) in memory.
+    let mut new_nums = Vec::with_capacity(nums.len() - 1);
+
+    // Running counters for what the final result will be for backwards
+    // and forwards interpolation
+    let mut backwards = 0;
+    let mut forwards = 0;
+
+    // For extrapolating backwards we need to flip the sign of what we
+    // add each "turn" to account for signs. This allows us to compute
+    // forwards & backwards in the same loop, minimising total allocations.
+    let mut flip = 1;
+
+    while nums.iter().any(|&x| x != 0) {
+        forwards += nums.last().expect("nums should not be empty");
+        backwards += flip * nums.first().expect("nums should not be empty");
+
+        new_nums.clear();
+
+        new_nums.extend(nums.iter().tuple_windows().map(|(x, y)| y - x));
+
+        // Buffer-swapping, always keeping just `nums` and `new_nums` as
+        // the only two large allocations.
+        std::mem::swap(&mut nums, &mut new_nums);
+
+        // Swap the sign for p2.
+        flip *= -1;
+    }
+
+    (backwards, forwards)
+}
+
+fn calculate_p1(data: &[Vec<i64>]) -> i64 {
+    data.iter().map(|nums| extrapolate(nums.clone()).1).sum()
+}
+
+fn calculate_p2(data: &[Vec<i64>]) -> i64 {
+    data.iter().map(|nums| extrapolate(nums.clone()).0).sum()
+}
+
+pub struct Day09;
+
+impl Solution for Day09 {
+    type Input<'a> = Vec<Vec<i64>>;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse<'a>(&self, raw_inp: &'a str) -> Result<Self::Input<'a>, ParseError> {
+        parse(raw_inp)
+    }
+
+    fn part1(&self, input: &Self::Input<'_>) -> i64 {
+        calculate_p1(input)
+    }
+
+    fn part2(&self, input: &Self::Input<'_>) -> Option<i64> {
+        Some(calculate_p2(input))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    aoc_tests! {
+        real_input: "2023_09";
+
+        p1 (part 1): |inp| calculate_p1(&parse(inp).unwrap()) => {
+            example("2023_09") => 114,
+        }
+
+        p2 (part 2): |inp| calculate_p2(&parse(inp).unwrap()) => {
+            example("2023_09") => 2,
+        }
+    }
+}
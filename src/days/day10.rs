@@ -0,0 +1,198 @@
+use crate::grid_util::{grid_error, render, Dir, Grid, Overlay, Pos, RenderFormat, Rgb};
+use crate::parse_util::ParseError;
+use crate::Solution;
+use ahash::AHashSet;
+
+pub struct Data {
+    grid: Grid<u8>,
+    main_loop: AHashSet<Pos>,
+}
+
+fn parse(raw_inp: &str) -> Result<Data, ParseError> {
+    let mut grid = Grid::parse_tiles(raw_inp, "|-LJ7F.S")?;
+
+    let start = grid
+        .find(|&d| d == b'S')
+        .ok_or_else(|| ParseError::new(raw_inp.trim(), "a start tile 'S'"))?;
+
+    if !replace_start(&mut grid, start) {
+        return Err(grid_error(
+            raw_inp,
+            start.into(),
+            "a start tile connected to exactly two pipes",
+        ));
+    }
+
+    let main_loop = get_main_loop(&grid, start);
+
+    Ok(Data { grid, main_loop })
+}
+
+fn get_rules_for(itm: u8) -> &'static [Dir] {
+    match itm {
+        b'|' => &[Dir::Up, Dir::Down],
+        b'-' => &[Dir::Right, Dir::Left],
+        b'L' => &[Dir::Up, Dir::Right],
+        b'J' => &[Dir::Up, Dir::Left],
+        b'7' => &[Dir::Down, Dir::Left],
+        b'F' => &[Dir::Down, Dir::Right],
+        _ => &[],
+    }
+}
+
+const CONNECT_LEFT: [u8; 3] = [b'F', b'L', b'-'];
+const CONNECT_RIGHT: [u8; 3] = [b'J', b'7', b'-'];
+const CONNECT_UP: [u8; 3] = [b'F', b'7', b'|'];
+const CONNECT_DOWN: [u8; 3] = [b'J', b'L', b'|'];
+
+// Returns whether the start could be replaced by a pipe
+fn replace_start(data: &mut Grid<u8>, start: Pos) -> bool {
+    let cons = [Dir::Up, Dir::Down, Dir::Left, Dir::Right]
+        .into_iter()
+        .zip([CONNECT_UP, CONNECT_DOWN, CONNECT_LEFT, CONNECT_RIGHT])
+        .map(|(dir, connections)| {
+            data.step(start, dir)
+                .is_some_and(|pt| connections.contains(&data[pt]))
+        })
+        .collect::<Vec<_>>();
+
+    let new = match (cons[0], cons[1], cons[2], cons[3]) {
+        (true, true, false, false) => b'|',
+        (true, false, true, false) => b'J',
+        (true, false, false, true) => b'L',
+        (false, false, true, true) => b'-',
+        (false, true, true, false) => b'7',
+        (false, true, false, true) => b'F',
+        _ => return false,
+    };
+
+    data[start] = new;
+    true
+}
+
+fn get_main_loop(data: &Grid<u8>, start: Pos) -> AHashSet<Pos> {
+    let mut visited = AHashSet::new();
+
+    let mut next = start;
+
+    loop {
+        visited.insert(next);
+        let n = get_rules_for(data[next])
+            .iter()
+            .filter_map(|&dir| data.step(next, dir))
+            .find(|p| !visited.contains(p));
+
+        if let Some(n) = n {
+            next = n;
+        } else {
+            break;
+        }
+    }
+
+    visited
+}
+
+fn inside_tiles<'a>(
+    data: &'a Grid<u8>,
+    main_loop: &'a AHashSet<Pos>,
+) -> impl Iterator<Item = Pos> + 'a {
+    (0..data.rows()).flat_map(move |y| {
+        let mut inside = false;
+
+        (0..data.columns()).filter_map(move |x| {
+            let pos = Pos { y, x };
+            let cell = data[pos];
+
+            let in_main_loop = main_loop.contains(&pos);
+
+            if in_main_loop && (cell == b'|' || cell == b'J' || cell == b'L') {
+                inside = !inside
+            }
+
+            (!in_main_loop && inside).then_some(pos)
+        })
+    })
+}
+
+fn calculate_inside(data: &Grid<u8>, main_loop: &AHashSet<Pos>) -> usize {
+    inside_tiles(data, main_loop).count()
+}
+
+fn symbol(cell: &u8) -> char {
+    match cell {
+        b'|' => '│',
+        b'-' => '─',
+        b'L' => '└',
+        b'J' => '┘',
+        b'7' => '┐',
+        b'F' => '┌',
+        _ => ' ',
+    }
+}
+
+fn colour(cell: &u8) -> Rgb {
+    match cell {
+        b'.' => [0, 0, 0],
+        _ => [80, 80, 80],
+    }
+}
+
+fn calculate_p1(data: &Data) -> usize {
+    data.main_loop.len() / 2
+}
+
+fn calculate_p2(data: &Data) -> usize {
+    calculate_inside(&data.grid, &data.main_loop)
+}
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    type Input<'a> = Data;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse<'a>(&self, raw_inp: &'a str) -> Result<Self::Input<'a>, ParseError> {
+        parse(raw_inp)
+    }
+
+    fn part1(&self, input: &Self::Input<'_>) -> usize {
+        calculate_p1(input)
+    }
+
+    fn part2(&self, input: &Self::Input<'_>) -> Option<usize> {
+        Some(calculate_p2(input))
+    }
+
+    // Pipes, with the tiles enclosed by the main loop marked
+    fn render(&self, input: &Self::Input<'_>, format: RenderFormat) -> Option<Vec<u8>> {
+        let inside = inside_tiles(&input.grid, &input.main_loop).collect();
+
+        let overlay = Overlay {
+            positions: &inside,
+            symbol: 'I',
+            colour: [0, 200, 0],
+        };
+
+        Some(render(&input.grid, format, symbol, colour, Some(&overlay)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    aoc_tests! {
+        real_input: "2023_10";
+
+        p1 (part 1): |inp| calculate_p1(&parse(inp).unwrap()) => {
+            example("2023_10_p1") => 8,
+        }
+
+        p2 (part 2): |inp| calculate_p2(&parse(inp).unwrap()) => {
+            example("2023_10_p2_1") => 4,
+            example_2("2023_10_p2_2") => 8,
+            example_3("2023_10_p2_3") => 10,
+        }
+    }
+}
@@ -0,0 +1,117 @@
+use crate::grid_util::make_bool_grid;
+use crate::parse_util::ParseError;
+use crate::Solution;
+use itertools::Itertools;
+
+#[derive(Eq, PartialEq)]
+struct Point {
+    pub x: usize,
+    pub y: usize,
+}
+
+pub struct Data {
+    galaxies: Vec<Point>,
+    extra_cols: Vec<usize>,
+    extra_rows: Vec<usize>,
+    rows: usize,
+    cols: usize,
+}
+
+fn parse(raw_inp: &str) -> Result<Data, ParseError> {
+    let grid = make_bool_grid::<b'#'>(raw_inp)?;
+
+    let galaxies = grid
+        .indexed_iter()
+        .filter(|(_, &v)| v)
+        .map(|(idx, _)| Point { y: idx.0, x: idx.1 })
+        .collect::<Vec<_>>();
+
+    let extra_rows = (0..grid.dim().0)
+        .filter(|&y| galaxies.iter().all(|g| g.y != y))
+        .collect::<Vec<_>>();
+
+    let extra_cols = (0..grid.dim().1)
+        .filter(|&x| galaxies.iter().all(|g| g.x != x))
+        .collect::<Vec<_>>();
+
+    Ok(Data {
+        galaxies,
+        extra_cols,
+        extra_rows,
+        rows: grid.dim().0,
+        cols: grid.dim().1,
+    })
+}
+
+fn make_cumulative_sum<const MUL: usize, F>(n: usize, add_many_rows: F) -> Vec<usize>
+where
+    F: Fn(usize) -> bool,
+{
+    let mut csum = Vec::with_capacity(n);
+
+    for i in 0..n {
+        let add_many = add_many_rows(i);
+        csum.push(csum.last().unwrap_or(&0) + if add_many { MUL } else { 1 });
+    }
+
+    csum
+}
+
+fn calculate<const MUL: usize>(data: &Data) -> usize {
+    let rows_csum = make_cumulative_sum::<MUL, _>(data.rows, |y| data.extra_rows.contains(&y));
+    let cols_csum = make_cumulative_sum::<MUL, _>(data.cols, |x| data.extra_cols.contains(&x));
+
+    data.galaxies
+        .iter()
+        .tuple_combinations()
+        .map(|(g1, g2)| {
+            let rows_cost = rows_csum[g1.y.max(g2.y)] - rows_csum[g1.y.min(g2.y)];
+            let cols_cost = cols_csum[g1.x.max(g2.x)] - cols_csum[g1.x.min(g2.x)];
+
+            rows_cost + cols_cost
+        })
+        .sum()
+}
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    type Input<'a> = Data;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse<'a>(&self, raw_inp: &'a str) -> Result<Self::Input<'a>, ParseError> {
+        parse(raw_inp)
+    }
+
+    fn part1(&self, input: &Self::Input<'_>) -> usize {
+        calculate::<2>(input)
+    }
+
+    fn part2(&self, input: &Self::Input<'_>) -> Option<usize> {
+        Some(calculate::<1000000>(input))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    aoc_tests! {
+        real_input: "2023_11";
+
+        p1 (part 1): |inp| calculate::<2>(&parse(inp).unwrap()) => {
+            example("2023_11") => 374,
+        }
+
+        p2_x10: |inp| calculate::<10>(&parse(inp).unwrap()) => {
+            example("2023_11") => 1030,
+        }
+
+        p2_x100: |inp| calculate::<100>(&parse(inp).unwrap()) => {
+            example("2023_11") => 8410,
+        }
+
+        p2 (part 2): |inp| calculate::<1000000>(&parse(inp).unwrap()) => {}
+    }
+}
@@ -0,0 +1,510 @@
+use crate::parse_util::{parse_number, split_once, ParseError};
+use crate::Solution;
+use num::{BigUint, One, Zero};
+use rayon::prelude::*;
+use std::ops::AddAssign;
+use std::str::FromStr;
+
+pub struct Data {
+    items: Vec<u8>,
+    counts: Vec<usize>,
+}
+
+impl Data {
+    /// A record of springs (each one of `.#?`) and the lengths of their runs of '#'.
+    pub fn new(items: Vec<u8>, counts: Vec<usize>) -> Data {
+        debug_assert!(items.iter().all(|b| b".#?".contains(b)), "invalid spring");
+        Data { items, counts }
+    }
+
+    /// Repeats the record `factor` times, joining the springs with `separator`.
+    pub fn unfold(&self, factor: usize, separator: u8) -> Data {
+        debug_assert!(b".#?".contains(&separator), "invalid separator");
+
+        let mut items = Vec::with_capacity(factor * (self.items.len() + 1));
+        for idx in 0..factor {
+            if idx > 0 {
+                items.push(separator);
+            }
+            items.extend_from_slice(&self.items);
+        }
+
+        Data {
+            items,
+            counts: self.counts.repeat(factor),
+        }
+    }
+}
+
+impl FromStr for Data {
+    type Err = ParseError;
+
+    fn from_str(line: &str) -> Result<Self, ParseError> {
+        let (items, counts) = split_once(line, " ")?;
+
+        if let Some(idx) = items.bytes().position(|b| !b".#?".contains(&b)) {
+            return Err(ParseError::new(&items[idx..], "a spring (one of .#?)"));
+        }
+
+        let c = counts
+            .split(',')
+            .map(parse_number)
+            .collect::<Result<Vec<usize>, _>>()?;
+
+        let i = items.bytes().collect::<Vec<_>>();
+
+        Ok(Data {
+            items: i,
+            counts: c,
+        })
+    }
+}
+
+fn parse(raw_inp: &str) -> Result<Vec<Data>, ParseError> {
+    raw_inp.trim().lines().map(|line| line.parse()).collect()
+}
+
+/// Number of ways to arrange `items[i..]` into the runs `counts[j..]`, for
+/// every `i` and `j`, where item `i` starts outside of a run.
+///
+/// Generic over the count type, since long unfolded records overflow `u64`.
+struct ArrangementTable<T> {
+    columns: usize,
+    // Number of '.' before each position, so a run can be checked in O(1)
+    dots_before: Vec<usize>,
+    ways: Vec<T>,
+}
+
+/// One way to fill in the springs at a position: either a single '.', or the
+/// next run of '#' along with the '.' after it.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Step {
+    Operational,
+    Run,
+}
+
+impl<T> ArrangementTable<T>
+where
+    T: Clone + Zero + One + for<'a> AddAssign<&'a T>,
+{
+    fn new(data: &Data) -> ArrangementTable<T> {
+        let (items, counts) = (&data.items, &data.counts);
+        let columns = counts.len() + 1;
+
+        let mut dots_before = Vec::with_capacity(items.len() + 1);
+        dots_before.push(0);
+        for &item in items {
+            dots_before.push(dots_before.last().unwrap() + usize::from(item == b'.'));
+        }
+
+        let mut table = ArrangementTable {
+            columns,
+            dots_before,
+            ways: vec![T::zero(); (items.len() + 1) * columns],
+        };
+        table.ways[items.len() * columns + counts.len()] = T::one();
+
+        // Fill in backwards, so everything after position i is known
+        for i in (0..items.len()).rev() {
+            for j in 0..columns {
+                let mut ways = T::zero();
+                for (_, next_i, next_j) in table.steps(data, i, j) {
+                    ways += table.get(next_i, next_j);
+                }
+                table.ways[i * columns + j] = ways;
+            }
+        }
+
+        table
+    }
+
+    /// Steps which could be taken from item `i` with `j` runs placed, and the
+    /// position each leads to. Doesn't check whether any arrangements follow.
+    fn steps(&self, data: &Data, i: usize, j: usize) -> impl Iterator<Item = (Step, usize, usize)> {
+        let (items, counts) = (&data.items, &data.counts);
+
+        let operational = (items[i] != b'#').then_some((Step::Operational, i + 1, j));
+
+        let run = (items[i] != b'.' && j < counts.len())
+            .then(|| i + counts[j])
+            .filter(|&end| {
+                end <= items.len()
+                    && self.dots_before[end] == self.dots_before[i]
+                    && items.get(end) != Some(&b'#')
+            })
+            // The run is followed by a '.' unless it's at the end of the record
+            .map(|end| (Step::Run, (end + 1).min(items.len()), j + 1));
+
+        operational.into_iter().chain(run)
+    }
+
+    fn get(&self, consumed_items: usize, consumed_counts: usize) -> &T {
+        &self.ways[consumed_items * self.columns + consumed_counts]
+    }
+
+    fn total(&self) -> &T {
+        self.get(0, 0)
+    }
+}
+
+/// Number of ways the unknown springs in a record could be filled in.
+pub fn count_arrangements<T>(data: &Data) -> T
+where
+    T: Clone + Zero + One + for<'a> AddAssign<&'a T>,
+{
+    ArrangementTable::<T>::new(data).total().clone()
+}
+
+/// The record with every spring which is the same in all of its arrangements
+/// filled in, or `None` if it has no arrangements.
+pub fn resolve_springs(data: &Data) -> Option<Vec<u8>> {
+    // Only whether a count is zero matters here, and f64 can't overflow
+    let table = ArrangementTable::<f64>::new(data);
+    if table.total().is_zero() {
+        return None;
+    }
+
+    let len = data.items.len();
+    let mut can_be_operational = vec![false; len];
+    let mut can_be_damaged = vec![false; len];
+
+    // Positions reachable from the start which still lead to an arrangement
+    let mut reachable = vec![false; table.ways.len()];
+    reachable[0] = true;
+
+    for i in 0..len {
+        for j in 0..table.columns {
+            if !reachable[i * table.columns + j] {
+                continue;
+            }
+
+            for (step, next_i, next_j) in table.steps(data, i, j) {
+                if table.get(next_i, next_j).is_zero() {
+                    continue;
+                }
+                reachable[next_i * table.columns + next_j] = true;
+
+                match step {
+                    Step::Operational => can_be_operational[i] = true,
+                    Step::Run => {
+                        let end = i + data.counts[j];
+                        can_be_damaged[i..end].fill(true);
+                        can_be_operational[end..next_i].fill(true);
+                    }
+                }
+            }
+        }
+    }
+
+    Some(
+        can_be_operational
+            .into_iter()
+            .zip(can_be_damaged)
+            .map(|options| match options {
+                (true, false) => b'.',
+                (false, true) => b'#',
+                _ => b'?',
+            })
+            .collect(),
+    )
+}
+
+// Writes the springs covered by a step from `i` to `next_i`, with `j` runs placed
+fn push_step(
+    springs: &mut Vec<u8>,
+    data: &Data,
+    step: Step,
+    (i, j): (usize, usize),
+    next_i: usize,
+) {
+    match step {
+        Step::Operational => springs.push(b'.'),
+        Step::Run => {
+            springs.extend(std::iter::repeat_n(b'#', data.counts[j]));
+            springs.resize(springs.len() + (next_i - i - data.counts[j]), b'.');
+        }
+    }
+}
+
+/// Every concrete arrangement of a record, in ascending order ('#' before '.'),
+/// produced lazily with a depth-first search which skips dead ends.
+pub struct Arrangements<'a> {
+    data: &'a Data,
+    table: ArrangementTable<BigUint>,
+    // Steps still to try: the step, where it starts and how long the springs were
+    stack: Vec<(Step, usize, usize, usize)>,
+    springs: Vec<u8>,
+    // An empty record has no steps, but one arrangement if it has no runs
+    empty_pending: bool,
+}
+
+impl<'a> Arrangements<'a> {
+    pub fn new(data: &'a Data) -> Arrangements<'a> {
+        let mut arrangements = Arrangements {
+            data,
+            table: ArrangementTable::new(data),
+            stack: vec![],
+            springs: Vec::with_capacity(data.items.len()),
+            empty_pending: false,
+        };
+        arrangements.empty_pending = data.items.is_empty() && !arrangements.total().is_zero();
+        arrangements.push_steps(0, 0);
+        arrangements
+    }
+
+    /// Number of arrangements in total, including any already produced.
+    pub fn total(&self) -> &BigUint {
+        self.table.total()
+    }
+
+    /// Draws one of the arrangements uniformly at random, or `None` if there
+    /// are none, reusing the table built for listing them. Doesn't affect
+    /// which arrangements are still to be listed.
+    pub fn sample(&self, rng: &mut fastrand::Rng) -> Option<String> {
+        sample_from(&self.table, self.data, rng)
+    }
+
+    fn push_steps(&mut self, i: usize, j: usize) {
+        if i == self.data.items.len() {
+            return;
+        }
+
+        // The stack is last-in first-out, so the run is tried before the '.'
+        let len = self.springs.len();
+        self.stack.extend(
+            self.table
+                .steps(self.data, i, j)
+                .filter(|&(_, next_i, next_j)| !self.table.get(next_i, next_j).is_zero())
+                .map(|(step, _, _)| (step, i, j, len)),
+        );
+    }
+}
+
+impl Iterator for Arrangements<'_> {
+    type Item = String;
+
+    fn next(&mut self) -> Option<String> {
+        if self.empty_pending {
+            self.empty_pending = false;
+            return Some(String::new());
+        }
+
+        while let Some((step, i, j, len)) = self.stack.pop() {
+            self.springs.truncate(len);
+
+            let (_, next_i, next_j) = self
+                .table
+                .steps(self.data, i, j)
+                .find(|&(s, _, _)| s == step)
+                .expect("step was valid when pushed");
+            push_step(&mut self.springs, self.data, step, (i, j), next_i);
+
+            if next_i == self.data.items.len() {
+                // Dead ends were skipped, so every run has been placed
+                return Some(String::from_utf8(self.springs.clone()).expect("springs are ASCII"));
+            }
+            self.push_steps(next_i, next_j);
+        }
+
+        None
+    }
+}
+
+// Uniformly random number below `bound`, by rejection sampling
+fn random_below(rng: &mut fastrand::Rng, bound: &BigUint) -> BigUint {
+    let bits = bound.bits();
+    let mut bytes = vec![0; bits.div_ceil(8) as usize];
+
+    loop {
+        rng.fill(&mut bytes);
+        if !bits.is_multiple_of(8) {
+            *bytes.last_mut().unwrap() &= (1 << (bits % 8)) - 1;
+        }
+
+        let candidate = BigUint::from_bytes_le(&bytes);
+        if &candidate < bound {
+            return candidate;
+        }
+    }
+}
+
+/// Draws one of a record's arrangements uniformly at random, or `None` if it
+/// has none. Works for records with far too many arrangements to list.
+///
+/// Builds a table of arrangements each time, so use `Arrangements::sample` to
+/// draw many samples from one record.
+pub fn sample_arrangement(data: &Data, rng: &mut fastrand::Rng) -> Option<String> {
+    sample_from(&ArrangementTable::new(data), data, rng)
+}
+
+fn sample_from(
+    table: &ArrangementTable<BigUint>,
+    data: &Data,
+    rng: &mut fastrand::Rng,
+) -> Option<String> {
+    if table.total().is_zero() {
+        return None;
+    }
+
+    let mut springs = Vec::with_capacity(data.items.len());
+    let (mut i, mut j) = (0, 0);
+
+    // Each step is chosen with probability proportional to the arrangements after it
+    while i < data.items.len() {
+        let mut pick = random_below(rng, table.get(i, j));
+        for (step, next_i, next_j) in table.steps(data, i, j) {
+            let ways = table.get(next_i, next_j);
+            if &pick < ways {
+                push_step(&mut springs, data, step, (i, j), next_i);
+                (i, j) = (next_i, next_j);
+                break;
+            }
+            pick -= ways;
+        }
+    }
+
+    Some(String::from_utf8(springs).expect("springs are ASCII"))
+}
+
+const UNFOLD_FACTOR: usize = 5;
+
+fn calculate_p1(data: &[Data]) -> u64 {
+    data.par_iter().map(count_arrangements::<u64>).sum()
+}
+
+fn calculate_p2(data: &[Data]) -> u64 {
+    data.par_iter()
+        .map(|d| count_arrangements::<u64>(&d.unfold(UNFOLD_FACTOR, b'?')))
+        .sum()
+}
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    type Input<'a> = Vec<Data>;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse<'a>(&self, raw_inp: &'a str) -> Result<Self::Input<'a>, ParseError> {
+        parse(raw_inp)
+    }
+
+    fn part1(&self, input: &Self::Input<'_>) -> u64 {
+        calculate_p1(input)
+    }
+
+    fn part2(&self, input: &Self::Input<'_>) -> Option<u64> {
+        Some(calculate_p2(input))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    aoc_tests! {
+        real_input: "2023_12";
+
+        p1 (part 1): |inp| calculate_p1(&parse(inp).unwrap()) => {
+            example("2023_12") => 21,
+        }
+
+        p2 (part 2): |inp| calculate_p2(&parse(inp).unwrap()) => {
+            example("2023_12") => 525152,
+        }
+    }
+
+    #[test]
+    fn test_large_unfold() {
+        let data = "?###???????? 3,2,1".parse::<Data>().unwrap();
+        assert_eq!(count_arrangements::<u64>(&data.unfold(5, b'?')), 506250);
+
+        // 10 * 15^(n-1) arrangements, which overflows u64 long before n = 40
+        let expected = |n: u32| num::BigUint::from(15u32).pow(n - 1) * 10u32;
+        assert_eq!(
+            count_arrangements::<num::BigUint>(&data.unfold(40, b'?')),
+            expected(40)
+        );
+        assert_eq!(
+            num::BigUint::from(count_arrangements::<u128>(&data.unfold(30, b'?'))),
+            expected(30)
+        );
+
+        // With a '.' separator, each copy is arranged independently
+        assert_eq!(count_arrangements::<u64>(&data.unfold(3, b'.')), 1000);
+    }
+
+    #[test]
+    fn test_resolve_springs() {
+        let resolve = |record: &str| {
+            resolve_springs(&record.parse().unwrap()).map(|s| String::from_utf8(s).unwrap())
+        };
+
+        assert_eq!(resolve("???.### 1,1,3").as_deref(), Some("#.#.###"));
+        assert_eq!(
+            resolve("?###???????? 3,2,1").as_deref(),
+            Some(".###.???????")
+        );
+        assert_eq!(resolve("????? 3").as_deref(), Some("??#??"));
+        assert_eq!(resolve("????? 1,3").as_deref(), Some("#.###"));
+        assert_eq!(resolve("#.# 2"), None);
+    }
+
+    #[test]
+    fn test_arrangements() {
+        let data = "?###???????? 3,2,1".parse::<Data>().unwrap();
+        let all = Arrangements::new(&data).collect::<Vec<_>>();
+        assert_eq!(all.len(), 10);
+        assert_eq!(all[0], ".###.##.#...");
+        assert_eq!(all[9], ".###....##.#");
+
+        let data = data.unfold(2, b'?');
+        let all = Arrangements::new(&data).collect::<Vec<_>>();
+        assert_eq!(BigUint::from(all.len()), *Arrangements::new(&data).total());
+        assert!(all.windows(2).all(|w| w[0] < w[1]));
+        for springs in all.iter() {
+            let record = format!("{} 3,2,1,3,2,1", springs);
+            assert_eq!(count_arrangements::<u64>(&record.parse().unwrap()), 1);
+        }
+
+        let none = "#.# 2".parse::<Data>().unwrap();
+        assert_eq!(Arrangements::new(&none).next(), None);
+        assert_eq!(sample_arrangement(&none, &mut fastrand::Rng::new()), None);
+    }
+
+    #[test]
+    fn test_sample_arrangement() {
+        let data = "?###???????? 3,2,1".parse::<Data>().unwrap();
+        let mut rng = fastrand::Rng::with_seed(12);
+
+        // Every arrangement turns up, roughly equally often
+        let mut seen = std::collections::HashMap::new();
+        for _ in 0..2000 {
+            *seen
+                .entry(sample_arrangement(&data, &mut rng).unwrap())
+                .or_insert(0) += 1;
+        }
+        assert_eq!(seen.len(), 10);
+        assert!(seen.values().all(|&n| (150..250).contains(&n)));
+
+        // Sampling from one table draws from the same arrangements
+        let arrangements = Arrangements::new(&data);
+        for _ in 0..20 {
+            assert!(seen.contains_key(&arrangements.sample(&mut rng).unwrap()));
+        }
+
+        // Far too many to list, but a sample still fits the record
+        let data = data.unfold(40, b'?');
+        let springs = sample_arrangement(&data, &mut rng).unwrap();
+        let counts = springs
+            .split('.')
+            .filter(|run| !run.is_empty())
+            .map(|run| run.len())
+            .collect::<Vec<_>>();
+        assert_eq!(counts, data.counts);
+        assert!(springs
+            .bytes()
+            .zip(data.items.iter())
+            .all(|(s, &item)| item == b'?' || item == s));
+    }
+}
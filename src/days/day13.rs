@@ -1,10 +1,9 @@
-#![cfg_attr(feature = "bench", feature(test))]
-use advent_of_code_2023::grid_util::make_bool_grid;
-use advent_of_code_2023::{Cli, Parser};
+use crate::grid_util::make_bool_grid;
+use crate::parse_util::ParseError;
+use crate::Solution;
 use ndarray::{s, Array2};
-use std::fs;
 
-fn parse(raw_inp: &str) -> Vec<Array2<bool>> {
+fn parse(raw_inp: &str) -> Result<Vec<Array2<bool>>, ParseError> {
     raw_inp
         .trim()
         .split("\n\n")
@@ -66,59 +65,39 @@ fn calculate<const WANTED_DIFF: usize>(data: &[Array2<bool>]) -> usize {
         .sum()
 }
 
-fn main() {
-    let args = Cli::parse();
+pub struct Day13;
 
-    let inp = fs::read_to_string(args.input).expect("can't open input file");
+impl Solution for Day13 {
+    type Input<'a> = Vec<Array2<bool>>;
+    type Answer1 = usize;
+    type Answer2 = usize;
 
-    let data = parse(&inp);
-    let p1 = calculate::<0>(&data);
-    let p2 = calculate::<1>(&data);
-    println!("{}\n{}", p1, p2);
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    const EXAMPLE_DATA: &str = include_str!("../../inputs/examples/2023_13");
-    const REAL_DATA: &str = include_str!("../../inputs/real/2023_13");
-
-    #[test]
-    fn test_p1_example() {
-        assert_eq!(calculate::<0>(&parse(EXAMPLE_DATA)), 405);
+    fn parse<'a>(&self, raw_inp: &'a str) -> Result<Self::Input<'a>, ParseError> {
+        parse(raw_inp)
     }
 
-    #[test]
-    fn test_p2_example() {
-        assert_eq!(calculate::<1>(&parse(EXAMPLE_DATA)), 400);
+    fn part1(&self, input: &Self::Input<'_>) -> usize {
+        calculate::<0>(input)
     }
 
-    #[test]
-    fn test_p1_real() {
-        assert_eq!(calculate::<0>(&parse(REAL_DATA)), 32723);
+    fn part2(&self, input: &Self::Input<'_>) -> Option<usize> {
+        Some(calculate::<1>(input))
     }
+}
 
-    #[test]
-    fn test_p2_real() {
-        assert_eq!(calculate::<1>(&parse(REAL_DATA)), 34536);
-    }
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    #[cfg(feature = "bench")]
-    mod benches {
-        extern crate test;
-        use test::{black_box, Bencher};
+    aoc_tests! {
+        real_input: "2023_13";
 
-        use super::*;
+        p1 (part 1): |inp| calculate::<0>(&parse(inp).unwrap()) => {
+            example("2023_13") => 405,
+        }
 
-        #[bench]
-        fn bench(b: &mut Bencher) {
-            b.iter(|| {
-                let data = parse(black_box(REAL_DATA));
-                let p1 = calculate::<0>(&data);
-                let p2 = calculate::<1>(&data);
-                (p1, p2)
-            });
+        p2 (part 2): |inp| calculate::<1>(&parse(inp).unwrap()) => {
+            example("2023_13") => 400,
         }
     }
 }
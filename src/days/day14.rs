@@ -1,12 +1,11 @@
-#![cfg_attr(feature = "bench", feature(test))]
-use advent_of_code_2023::grid_util::make_byte_grid;
-use advent_of_code_2023::{Cli, Parser};
+use crate::grid_util::{make_tile_grid, render, Grid, RenderFormat, Rgb};
+use crate::parse_util::ParseError;
+use crate::Solution;
 use ahash::AHashMap;
 use ndarray::{Array2, ArrayBase, Dim, ViewRepr};
-use std::fs;
 
-fn parse(raw_inp: &str) -> Array2<u8> {
-    make_byte_grid(raw_inp.trim())
+fn parse(raw_inp: &str) -> Result<Array2<u8>, ParseError> {
+    make_tile_grid(raw_inp, "O#.")
 }
 
 const NORTH: u8 = 0;
@@ -69,12 +68,22 @@ fn calculate_total_load(data: &Array2<u8>) -> usize {
         .sum()
 }
 
-fn calculate_p1(orig_data: &Array2<u8>) -> usize {
+fn tilt_north(orig_data: &Array2<u8>) -> Array2<u8> {
     let mut data = orig_data.clone();
-
     roll::<NORTH>(&mut data);
+    data
+}
+
+fn calculate_p1(orig_data: &Array2<u8>) -> usize {
+    calculate_total_load(&tilt_north(orig_data))
+}
 
-    calculate_total_load(&data)
+fn colour(cell: &u8) -> Rgb {
+    match cell {
+        b'O' => [230, 230, 230],
+        b'#' => [90, 70, 50],
+        _ => [0, 0, 0],
+    }
 }
 
 fn apply_one_cycle(data: &mut Array2<u8>) {
@@ -87,7 +96,8 @@ fn apply_one_cycle(data: &mut Array2<u8>) {
 // Tried hare-and-tortoise algorithm but that's slower
 // as the cycle just isn't very long and f() is expensive.
 // So just use a map of all previous states instead (!).
-fn calculate_p2(mut data: Array2<u8>) -> usize {
+fn calculate_p2(orig_data: &Array2<u8>) -> usize {
+    let mut data = orig_data.clone();
     let mut map: AHashMap<Array2<u8>, usize> = AHashMap::with_capacity(128);
 
     let mut curr = 0;
@@ -114,59 +124,45 @@ fn calculate_p2(mut data: Array2<u8>) -> usize {
     calculate_total_load(final_grid)
 }
 
-fn main() {
-    let args = Cli::parse();
-
-    let inp = fs::read_to_string(args.input).expect("can't open input file");
+pub struct Day14;
 
-    let data = parse(&inp);
-    let p1 = calculate_p1(&data);
-    let p2 = calculate_p2(data);
-    println!("{}\n{}", p1, p2);
-}
+impl Solution for Day14 {
+    type Input<'a> = Array2<u8>;
+    type Answer1 = usize;
+    type Answer2 = usize;
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    const EXAMPLE_DATA: &str = include_str!("../../inputs/examples/2023_14");
-    const REAL_DATA: &str = include_str!("../../inputs/real/2023_14");
-
-    #[test]
-    fn test_p1_example() {
-        assert_eq!(calculate_p1(&parse(EXAMPLE_DATA)), 136);
+    fn parse<'a>(&self, raw_inp: &'a str) -> Result<Self::Input<'a>, ParseError> {
+        parse(raw_inp)
     }
 
-    #[test]
-    fn test_p2_example() {
-        assert_eq!(calculate_p2(parse(EXAMPLE_DATA)), 64);
+    fn part1(&self, input: &Self::Input<'_>) -> usize {
+        calculate_p1(input)
     }
 
-    #[test]
-    fn test_p1_real() {
-        assert_eq!(calculate_p1(&parse(REAL_DATA)), 112048);
+    fn part2(&self, input: &Self::Input<'_>) -> Option<usize> {
+        Some(calculate_p2(input))
     }
 
-    #[test]
-    fn test_p2_real() {
-        assert_eq!(calculate_p2(parse(REAL_DATA)), 105606);
+    // The platform after tilting it north for part 1
+    fn render(&self, input: &Self::Input<'_>, format: RenderFormat) -> Option<Vec<u8>> {
+        let grid = Grid::new(tilt_north(input));
+        Some(render(&grid, format, |&c| c as char, colour, None))
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    aoc_tests! {
+        real_input: "2023_14";
+
+        p1 (part 1): |inp| calculate_p1(&parse(inp).unwrap()) => {
+            example("2023_14") => 136,
+        }
 
-    #[cfg(feature = "bench")]
-    mod benches {
-        extern crate test;
-        use test::{black_box, Bencher};
-
-        use super::*;
-
-        #[bench]
-        fn bench(b: &mut Bencher) {
-            b.iter(|| {
-                let data = parse(black_box(REAL_DATA));
-                let p1 = calculate_p1(&data);
-                let p2 = calculate_p2(data);
-                (p1, p2)
-            });
+        p2 (part 2): |inp| calculate_p2(&parse(inp).unwrap()) => {
+            example("2023_14") => 64,
         }
     }
 }
@@ -0,0 +1,119 @@
+use crate::parse_util::{parse_number, ParseError};
+use crate::Solution;
+
+pub struct Step<'a> {
+    raw: &'a str,
+    label: &'a str,
+    // None for a '-' (remove) step
+    focal_length: Option<usize>,
+}
+
+fn parse_step(raw: &str) -> Result<Step<'_>, ParseError> {
+    if let Some((label, value)) = raw.split_once('=') {
+        Ok(Step {
+            raw,
+            label,
+            focal_length: Some(parse_number(value)?),
+        })
+    } else if let Some(label) = raw.strip_suffix('-') {
+        Ok(Step {
+            raw,
+            label,
+            focal_length: None,
+        })
+    } else {
+        Err(ParseError::new(&raw[raw.len()..], "'=' or '-'"))
+    }
+}
+
+fn parse(raw_inp: &str) -> Result<Vec<Step<'_>>, ParseError> {
+    raw_inp.trim().split(',').map(parse_step).collect()
+}
+
+fn hash(s: &str) -> usize {
+    s.bytes()
+        .fold(0, |acc, elem| ((acc + elem as usize) * 17) % 256)
+}
+
+fn calculate_p1(data: &[Step]) -> usize {
+    data.iter().map(|s| hash(s.raw)).sum()
+}
+
+fn calculate_p2_score(library: &[Vec<(&str, usize)>; 256]) -> usize {
+    library
+        .iter()
+        .enumerate()
+        .map(|(k, v)| {
+            v.iter()
+                .enumerate()
+                .map(|(idx, itm)| (k + 1) * (idx + 1) * itm.1)
+                .sum::<usize>()
+        })
+        .sum()
+}
+
+fn calculate_p2(data: &[Step]) -> usize {
+    const EMPTY_VEC: Vec<(&str, usize)> = vec![];
+    let mut library: [Vec<(&str, usize)>; 256] = [EMPTY_VEC; 256];
+
+    data.iter().for_each(|s| {
+        let key = s.label;
+        if let Some(new_value) = s.focal_length {
+            let bx = hash(key);
+            let mut is_new = true;
+
+            for (label, val) in &mut library[bx] {
+                if &key == label {
+                    is_new = false;
+                    *val = new_value;
+                    break;
+                }
+            }
+
+            if is_new {
+                library[bx].push((key, new_value));
+            }
+        } else {
+            library[hash(key)].retain(|elem| elem.0 != key);
+        }
+    });
+
+    calculate_p2_score(&library)
+}
+
+pub struct Day15;
+
+impl Solution for Day15 {
+    type Input<'a> = Vec<Step<'a>>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse<'a>(&self, raw_inp: &'a str) -> Result<Self::Input<'a>, ParseError> {
+        parse(raw_inp)
+    }
+
+    fn part1(&self, input: &Self::Input<'_>) -> usize {
+        calculate_p1(input)
+    }
+
+    fn part2(&self, input: &Self::Input<'_>) -> Option<usize> {
+        Some(calculate_p2(input))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    aoc_tests! {
+        real_input: "2023_15";
+
+        p1 (part 1): |inp| calculate_p1(&parse(inp).unwrap()) => {
+            example("2023_15") => 1320,
+        }
+
+        p2 (part 2): |inp| calculate_p2(&parse(inp).unwrap()) => {
+            example("2023_15") => 145,
+        }
+    }
+}
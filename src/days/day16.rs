@@ -0,0 +1,190 @@
+use crate::grid_util::{render, Dir, Grid, Overlay, Pos, RenderFormat, Rgb};
+use crate::parse_util::ParseError;
+use crate::Solution;
+use ahash::AHashSet;
+use bitvec::prelude::*;
+use rayon::prelude::*;
+
+fn parse(raw_inp: &str) -> Result<Grid<u8>, ParseError> {
+    Grid::parse_tiles(raw_inp, "./\\|-")
+}
+
+struct SeenStartLocations {
+    y_size: usize,
+    x_size: usize,
+    seen: BitVec<u32>,
+}
+
+impl SeenStartLocations {
+    fn new(dims: (usize, usize)) -> SeenStartLocations {
+        SeenStartLocations {
+            y_size: dims.0,
+            x_size: dims.1,
+            seen: bitvec![u32, Lsb0; 0; dims.0 * dims.1 * 4],
+        }
+    }
+
+    // Returns whether this location was already set
+    fn insert(&mut self, loc: Pos, dir: Dir) -> bool {
+        let mut result = false;
+        if loc.y < self.y_size && loc.x < self.x_size {
+            let idx = loc.y * self.x_size * 4 + loc.x * 4 + dir.index();
+            result = *self.seen.get(idx).unwrap();
+            self.seen.set(idx, true);
+        }
+        result
+    }
+}
+
+// Returns which tiles the beam passes through, indexed by y * columns + x
+fn energise(data: &Grid<u8>, initial_pos: Pos, initial_dir: Dir) -> BitVec<u32> {
+    let fake_start = initial_pos.wrapping_step(initial_dir.reverse());
+
+    let mut starts = Vec::with_capacity(32);
+    starts.push((fake_start, initial_dir));
+
+    let mut energised = bitvec![u32, Lsb0; 0; data.rows() * data.columns()];
+    let mut seen_starts = SeenStartLocations::new(data.dim());
+
+    while let Some((start_pos, dir)) = starts.pop() {
+        // If this start/dir combination has been seen already, don't process it.
+        // Beam can go in an infinite loop
+        if seen_starts.insert(start_pos, dir) {
+            continue;
+        }
+
+        let mut pos = start_pos.wrapping_step(dir);
+
+        while let Some(&grid_cell) = data.get(pos) {
+            energised.set(pos.y * data.columns() + pos.x, true);
+
+            match (dir, grid_cell) {
+                (Dir::Up | Dir::Down, b'-') => {
+                    starts.push((pos, Dir::Right));
+                    starts.push((pos, Dir::Left));
+                    break;
+                }
+                (Dir::Right | Dir::Left, b'|') => {
+                    starts.push((pos, Dir::Up));
+                    starts.push((pos, Dir::Down));
+                    break;
+                }
+                (_, b'/') => {
+                    starts.push((
+                        pos,
+                        match dir {
+                            Dir::Up | Dir::Down => dir.turn_right(),
+                            Dir::Right | Dir::Left => dir.turn_left(),
+                        },
+                    ));
+                    break;
+                }
+                (_, b'\\') => {
+                    starts.push((
+                        pos,
+                        match dir {
+                            Dir::Up | Dir::Down => dir.turn_left(),
+                            Dir::Right | Dir::Left => dir.turn_right(),
+                        },
+                    ));
+                    break;
+                }
+                _ => {
+                    pos = pos.wrapping_step(dir);
+                }
+            }
+        }
+    }
+
+    energised
+}
+
+fn simulate(data: &Grid<u8>, initial_pos: Pos, initial_dir: Dir) -> usize {
+    energise(data, initial_pos, initial_dir).count_ones()
+}
+
+fn calculate_p1(data: &Grid<u8>) -> usize {
+    simulate(data, Pos::new(0, 0), Dir::Right)
+}
+
+fn calculate_p2(data: &Grid<u8>) -> usize {
+    (0..data.rows())
+        .into_par_iter()
+        .flat_map(|d| {
+            [
+                (Pos::new(d, 0), Dir::Right),
+                (Pos::new(d, data.columns() - 1), Dir::Left),
+                (Pos::new(0, d), Dir::Down),
+                (Pos::new(data.rows() - 1, d), Dir::Up),
+            ]
+        })
+        .map(|(start, dir)| simulate(data, start, dir))
+        .max()
+        .expect("should have an element")
+}
+
+fn colour(cell: &u8) -> Rgb {
+    match cell {
+        b'.' => [0, 0, 0],
+        _ => [120, 120, 140],
+    }
+}
+
+pub struct Day16;
+
+impl Solution for Day16 {
+    type Input<'a> = Grid<u8>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse<'a>(&self, raw_inp: &'a str) -> Result<Self::Input<'a>, ParseError> {
+        parse(raw_inp)
+    }
+
+    fn part1(&self, input: &Self::Input<'_>) -> usize {
+        calculate_p1(input)
+    }
+
+    fn part2(&self, input: &Self::Input<'_>) -> Option<usize> {
+        Some(calculate_p2(input))
+    }
+
+    // Tiles energised by the part 1 beam
+    fn render(&self, input: &Self::Input<'_>, format: RenderFormat) -> Option<Vec<u8>> {
+        let energised = energise(input, Pos::new(0, 0), Dir::Right)
+            .iter_ones()
+            .map(|idx| Pos::new(idx / input.columns(), idx % input.columns()))
+            .collect::<AHashSet<_>>();
+
+        let overlay = Overlay {
+            positions: &energised,
+            symbol: '#',
+            colour: [255, 200, 0],
+        };
+
+        Some(render(
+            input,
+            format,
+            |&c| c as char,
+            colour,
+            Some(&overlay),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    aoc_tests! {
+        real_input: "2023_16";
+
+        p1 (part 1): |inp| calculate_p1(&parse(inp).unwrap()) => {
+            example("2023_16") => 46,
+        }
+
+        p2 (part 2): |inp| calculate_p2(&parse(inp).unwrap()) => {
+            example("2023_16") => 51,
+        }
+    }
+}
@@ -0,0 +1,600 @@
+use crate::grid_util::{render, Dir, Grid, Pos, RenderFormat, Rgb};
+use crate::parse_util::ParseError;
+use crate::pathfind::{shortest_path, shortest_route, BucketQueue, Graph};
+use crate::Solution;
+use std::fmt;
+
+pub fn parse(raw_inp: &str) -> Result<Grid<u8>, ParseError> {
+    Grid::parse(raw_inp, "a digit", |b| b.is_ascii_digit().then(|| b - b'0'))
+}
+
+/// A direction of travel: one of the 8 compass directions, clockwise from up.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub struct Heading(u8);
+
+impl Heading {
+    const DELTAS: [(isize, isize); 8] = [
+        (-1, 0),
+        (-1, 1),
+        (0, 1),
+        (1, 1),
+        (1, 0),
+        (1, -1),
+        (0, -1),
+        (-1, -1),
+    ];
+
+    const ORTHOGONAL: [Heading; 4] = [Heading(0), Heading(2), Heading(4), Heading(6)];
+    const ALL: [Heading; 8] = [
+        Heading(0),
+        Heading(1),
+        Heading(2),
+        Heading(3),
+        Heading(4),
+        Heading(5),
+        Heading(6),
+        Heading(7),
+    ];
+
+    pub fn delta(self) -> (isize, isize) {
+        Heading::DELTAS[self.0 as usize]
+    }
+
+    pub fn reverse(self) -> Heading {
+        Heading((self.0 + 4) % 8)
+    }
+
+    fn arrow(self) -> char {
+        ['↑', '↗', '→', '↘', '↓', '↙', '←', '↖'][self.0 as usize]
+    }
+}
+
+impl From<Dir> for Heading {
+    fn from(dir: Dir) -> Heading {
+        Heading::ORTHOGONAL[dir.index()]
+    }
+}
+
+/// How a crucible is allowed to move, and where from and to.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Rules {
+    /// Blocks it must move in a straight line before turning or stopping.
+    pub min_run: usize,
+    /// Blocks it can move in a straight line before it has to turn.
+    pub max_run: usize,
+    /// Whether it can turn around, which counts as a turn.
+    pub reverse: bool,
+    /// Whether it can move diagonally, as well as up, down, left and right.
+    pub diagonals: bool,
+    /// Defaults to the top-left block.
+    pub start: Option<Pos>,
+    /// Defaults to the bottom-right block.
+    pub goal: Option<Pos>,
+}
+
+impl Rules {
+    pub const PART_1: Rules = Rules {
+        min_run: 1,
+        max_run: 3,
+        reverse: false,
+        diagonals: false,
+        start: None,
+        goal: None,
+    };
+
+    pub const PART_2: Rules = Rules {
+        min_run: 4,
+        max_run: 10,
+        ..Rules::PART_1
+    };
+
+    pub fn start(&self) -> Pos {
+        self.start.unwrap_or(Pos::new(0, 0))
+    }
+
+    pub fn goal(&self, grid: &Grid<u8>) -> Pos {
+        self.goal
+            .unwrap_or(Pos::new(grid.rows() - 1, grid.columns() - 1))
+    }
+
+    /// Checks the rules make sense for `grid`.
+    pub fn validate(&self, grid: &Grid<u8>) -> Result<(), String> {
+        if self.max_run == 0 {
+            return Err("the maximum run must be at least 1".to_string());
+        }
+        // No straight line is longer than the grid, and the search's table
+        // of states grows with the maximum run
+        let longest = grid.rows().max(grid.columns());
+        if self.max_run > longest {
+            return Err(format!(
+                "the maximum run ({}) is more than the {}x{} grid allows ({})",
+                self.max_run,
+                grid.rows(),
+                grid.columns(),
+                longest
+            ));
+        }
+        if self.min_run > self.max_run {
+            return Err(format!(
+                "the minimum run ({}) is more than the maximum ({})",
+                self.min_run, self.max_run
+            ));
+        }
+        for (name, pos) in [("start", self.start()), ("goal", self.goal(grid))] {
+            if !grid.contains(pos) {
+                return Err(format!(
+                    "{} {},{} is outside the {}x{} grid",
+                    name,
+                    pos.y,
+                    pos.x,
+                    grid.rows(),
+                    grid.columns()
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    fn headings(&self) -> &'static [Heading] {
+        if self.diagonals {
+            &Heading::ALL
+        } else {
+            &Heading::ORTHOGONAL
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+struct State {
+    pos: Pos,
+    heading: Heading,
+    run: usize,
+}
+
+struct Crucible<'a> {
+    grid: &'a Grid<u8>,
+    rules: &'a Rules,
+    goal: Pos,
+    // Least heat lost entering any block, for the heuristic
+    min_heat: usize,
+}
+
+impl<'a> Crucible<'a> {
+    fn new(grid: &'a Grid<u8>, rules: &'a Rules) -> Self {
+        Crucible {
+            grid,
+            rules,
+            goal: rules.goal(grid),
+            min_heat: grid.as_array().iter().min().copied().unwrap_or(0) as usize,
+        }
+    }
+
+    // Nothing moved yet, but facing in every direction
+    fn starts(&self) -> impl Iterator<Item = State> + '_ {
+        let pos = self.rules.start();
+        self.rules.headings().iter().map(move |&heading| State {
+            pos,
+            heading,
+            run: 0,
+        })
+    }
+}
+
+impl Graph for Crucible<'_> {
+    type Node = State;
+
+    fn num_nodes(&self) -> usize {
+        let headings = self.rules.headings().len();
+        (self.rules.max_run + 1) * headings * self.grid.rows() * self.grid.columns()
+    }
+
+    // (run, heading, y, x), where orthogonal headings are numbered 0-3 when
+    // they're the only ones allowed
+    fn index(&self, state: State) -> usize {
+        let headings = self.rules.headings().len();
+        let heading = state.heading.0 as usize * headings / Heading::ALL.len();
+        let idx = state.run * headings + heading;
+        (idx * self.grid.rows() + state.pos.y) * self.grid.columns() + state.pos.x
+    }
+
+    fn neighbours(&self, state: State, mut visit: impl FnMut(State, usize)) {
+        for &heading in self.rules.headings() {
+            if heading == state.heading.reverse() && !self.rules.reverse {
+                continue;
+            }
+
+            let straight = heading == state.heading;
+
+            if !straight && state.run < self.rules.min_run {
+                continue;
+            }
+            if straight && state.run >= self.rules.max_run {
+                continue;
+            }
+
+            let (dy, dx) = heading.delta();
+            if let Some(next_pos) = self.grid.checked_offset(state.pos, dy, dx) {
+                let run = if straight { state.run + 1 } else { 1 };
+
+                visit(
+                    State {
+                        pos: next_pos,
+                        heading,
+                        run,
+                    },
+                    self.grid[next_pos] as usize,
+                );
+            }
+        }
+    }
+
+    fn is_goal(&self, state: State) -> bool {
+        state.pos == self.goal && state.run >= self.rules.min_run
+    }
+
+    // Each move is to a neighbouring block, losing at least the least heat of any block
+    fn heuristic(&self, state: State) -> usize {
+        let (dy, dx) = (
+            self.goal.y.abs_diff(state.pos.y),
+            self.goal.x.abs_diff(state.pos.x),
+        );
+        let moves = if self.rules.diagonals {
+            dy.max(dx)
+        } else {
+            dy + dx
+        };
+        moves * self.min_heat
+    }
+}
+
+// Steps cost at most 9, plus up to 9 more for the heuristic
+type Queue = BucketQueue<32, (usize, State)>;
+
+/// Least heat a crucible following `rules` can lose, or `None` if it can't
+/// reach the goal at all. The rules must be valid for the grid.
+pub fn min_heat_loss(data: &Grid<u8>, rules: &Rules) -> Option<usize> {
+    let crucible = Crucible::new(data, rules);
+    shortest_path(&crucible, crucible.starts(), Queue::new()).map(|(cost, _)| cost)
+}
+
+/// The blocks a crucible moves into, in order, along with the direction it
+/// moved in to get to each one.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Route {
+    pub start: Pos,
+    pub moves: Vec<(Pos, Heading)>,
+}
+
+/// The route with the least heat loss, and its heat loss, or `None` if the
+/// crucible can't reach the goal. The rules must be valid for the grid.
+pub fn best_route(data: &Grid<u8>, rules: &Rules) -> Option<(usize, Route)> {
+    let crucible = Crucible::new(data, rules);
+    let (cost, states) = shortest_route(&crucible, crucible.starts(), Queue::new())?;
+
+    let route = Route {
+        start: states[0].pos,
+        moves: states[1..].iter().map(|s| (s.pos, s.heading)).collect(),
+    };
+    Some((cost, route))
+}
+
+/// Why a route breaks the crucible's rules. Moves are numbered from 0.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RouteError {
+    WrongStart(Pos),
+    WrongEnd(Pos),
+    /// The move isn't one block in its direction, leaves the grid, or is a
+    /// diagonal move when those aren't allowed.
+    NotAStep(usize),
+    Reversed(usize),
+    /// The crucible turns, or stops, before moving the minimum distance straight.
+    RunTooShort(usize),
+    RunTooLong(usize),
+}
+
+impl fmt::Display for RouteError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RouteError::WrongStart(pos) => {
+                write!(f, "starts at {},{}, not the start", pos.y, pos.x)
+            }
+            RouteError::WrongEnd(pos) => write!(f, "ends at {},{}, not the goal", pos.y, pos.x),
+            RouteError::NotAStep(idx) => write!(f, "move {} isn't a step to a neighbour", idx),
+            RouteError::Reversed(idx) => write!(f, "move {} reverses", idx),
+            RouteError::RunTooShort(idx) => write!(f, "move {} ends a run which is too short", idx),
+            RouteError::RunTooLong(idx) => write!(f, "move {} makes a run too long", idx),
+        }
+    }
+}
+
+/// Checks that a route follows the crucible's rules, returning its heat loss.
+pub fn verify_route(data: &Grid<u8>, rules: &Rules, route: &Route) -> Result<usize, RouteError> {
+    if route.start != rules.start() {
+        return Err(RouteError::WrongStart(route.start));
+    }
+
+    let mut pos = route.start;
+    let mut heat_loss = 0;
+    // Direction and length of the current straight run
+    let mut run: Option<(Heading, usize)> = None;
+
+    for (idx, &(next, heading)) in route.moves.iter().enumerate() {
+        let (dy, dx) = heading.delta();
+        if !rules.headings().contains(&heading) || data.checked_offset(pos, dy, dx) != Some(next) {
+            return Err(RouteError::NotAStep(idx));
+        }
+
+        run = match run {
+            Some((last, count)) if last == heading => {
+                if count >= rules.max_run {
+                    return Err(RouteError::RunTooLong(idx));
+                }
+                Some((heading, count + 1))
+            }
+            Some((last, count)) => {
+                if heading == last.reverse() && !rules.reverse {
+                    return Err(RouteError::Reversed(idx));
+                }
+                if count < rules.min_run {
+                    return Err(RouteError::RunTooShort(idx));
+                }
+                Some((heading, 1))
+            }
+            None => Some((heading, 1)),
+        };
+
+        heat_loss += data[next] as usize;
+        pos = next;
+    }
+
+    if pos != rules.goal(data) {
+        return Err(RouteError::WrongEnd(pos));
+    }
+    if run.map_or(0, |(_, count)| count) < rules.min_run {
+        return Err(RouteError::RunTooShort(route.moves.len()));
+    }
+
+    Ok(heat_loss)
+}
+
+/// Renders a route as arrows over the heat loss of each block.
+pub fn render_route(data: &Grid<u8>, route: &Route, format: RenderFormat) -> Vec<u8> {
+    let mut cells = Grid::new(data.as_array().map(|&heat| (heat, None)));
+    for &(pos, heading) in route.moves.iter() {
+        cells[pos].1 = Some(heading);
+    }
+
+    render(&cells, format, symbol, colour, None)
+}
+
+fn symbol(&(heat, heading): &(u8, Option<Heading>)) -> char {
+    match heading {
+        Some(heading) => heading.arrow(),
+        None => char::from(b'0' + heat),
+    }
+}
+
+// Darker for more heat loss, with the route in red
+fn colour(&(heat, heading): &(u8, Option<Heading>)) -> Rgb {
+    match heading {
+        Some(_) => [220, 40, 20],
+        None => [255 - heat * 25; 3],
+    }
+}
+
+fn calculate(data: &Grid<u8>, rules: &Rules) -> Result<usize, String> {
+    min_heat_loss(data, rules).ok_or_else(|| "the crucible can't reach the goal".to_string())
+}
+
+fn calculate_p1(data: &Grid<u8>) -> Result<usize, String> {
+    calculate(data, &Rules::PART_1)
+}
+
+fn calculate_p2(data: &Grid<u8>) -> Result<usize, String> {
+    calculate(data, &Rules::PART_2)
+}
+
+pub struct Day17;
+
+impl Solution for Day17 {
+    type Input<'a> = Grid<u8>;
+    // The least heat loss, or why there's no route
+    type Answer1 = Result<usize, String>;
+    type Answer2 = Result<usize, String>;
+
+    fn parse<'a>(&self, raw_inp: &'a str) -> Result<Self::Input<'a>, ParseError> {
+        parse(raw_inp)
+    }
+
+    fn part1(&self, input: &Self::Input<'_>) -> Result<usize, String> {
+        calculate_p1(input)
+    }
+
+    fn part2(&self, input: &Self::Input<'_>) -> Option<Result<usize, String>> {
+        Some(calculate_p2(input))
+    }
+
+    /// Draws part 2's route as arrows over the heat loss of each block, or
+    /// just the heat loss if there's no route.
+    fn render(&self, input: &Self::Input<'_>, format: RenderFormat) -> Option<Vec<u8>> {
+        let route = match best_route(input, &Rules::PART_2) {
+            Some((heat_loss, route)) => {
+                debug_assert_eq!(verify_route(input, &Rules::PART_2, &route), Ok(heat_loss));
+                route
+            }
+            None => Route {
+                start: Rules::PART_2.start(),
+                moves: vec![],
+            },
+        };
+
+        Some(render_route(input, &route, format))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    aoc_tests! {
+        real_input: "2023_17";
+
+        p1 (part 1): |inp| calculate_p1(&parse(inp).unwrap()).unwrap() => {
+            example("2023_17") => 102,
+        }
+
+        p2 (part 2): |inp| calculate_p2(&parse(inp).unwrap()).unwrap() => {
+            example("2023_17") => 94,
+            example_2("2023_17_2") => 71,
+        }
+    }
+
+    fn rules(min_run: usize, max_run: usize) -> Rules {
+        Rules {
+            min_run,
+            max_run,
+            ..Rules::PART_1
+        }
+    }
+
+    #[test]
+    fn test_best_route() {
+        let data = parse(fixture!("2023_17")).unwrap();
+
+        let (heat_loss, route) = best_route(&data, &Rules::PART_1).unwrap();
+        assert_eq!(heat_loss, 102);
+        assert_eq!(verify_route(&data, &Rules::PART_1, &route), Ok(102));
+        assert_eq!(route.moves[0], (Pos::new(0, 1), Heading::from(Dir::Right)));
+
+        let (_, route) = best_route(&data, &Rules::PART_2).unwrap();
+        assert_eq!(verify_route(&data, &Rules::PART_2, &route), Ok(94));
+        assert!(matches!(
+            verify_route(&data, &Rules::PART_1, &route),
+            Err(RouteError::RunTooLong(_))
+        ));
+    }
+
+    #[test]
+    fn test_rules() {
+        let data = parse(fixture!("2023_17")).unwrap();
+
+        // Relaxing any rule can only help
+        for relaxed in [
+            Rules {
+                reverse: true,
+                ..Rules::PART_1
+            },
+            Rules {
+                diagonals: true,
+                ..Rules::PART_1
+            },
+            rules(1, 10),
+        ] {
+            let (heat_loss, route) = best_route(&data, &relaxed).unwrap();
+            assert!(heat_loss <= 102);
+            assert_eq!(verify_route(&data, &relaxed, &route), Ok(heat_loss));
+        }
+
+        let diagonal = Rules {
+            diagonals: true,
+            ..Rules::PART_1
+        };
+        let (heat_loss, route) = best_route(&data, &diagonal).unwrap();
+        assert!(heat_loss < 102);
+        assert!(matches!(
+            verify_route(&data, &Rules::PART_1, &route),
+            Err(RouteError::NotAStep(_))
+        ));
+
+        // Going back to the start from the goal
+        let backwards = Rules {
+            start: Some(Pos::new(12, 12)),
+            goal: Some(Pos::new(0, 0)),
+            ..Rules::PART_1
+        };
+        assert_eq!(backwards.validate(&data), Ok(()));
+        assert!(rules(1, 13).validate(&data).is_ok());
+        assert!(rules(1, 14).validate(&data).is_err());
+        let (heat_loss, route) = best_route(&data, &backwards).unwrap();
+        assert_eq!(route.moves.last().unwrap().0, Pos::new(0, 0));
+        assert_eq!(verify_route(&data, &backwards, &route), Ok(heat_loss));
+
+        assert!(rules(4, 3).validate(&data).is_err());
+        assert!(rules(0, 0).validate(&data).is_err());
+        let outside = Rules {
+            goal: Some(Pos::new(13, 0)),
+            ..Rules::PART_1
+        };
+        assert!(outside.validate(&data).is_err());
+
+        // A single row with a minimum run longer than it
+        let row = parse("11111\n").unwrap();
+        assert_eq!(min_heat_loss(&row, &rules(5, 10)), None);
+        assert_eq!(min_heat_loss(&row, &rules(4, 10)), Some(4));
+
+        // The crucible has to move, so can't finish where it started
+        let block = parse("1\n").unwrap();
+        assert_eq!(
+            calculate_p1(&block),
+            Err("the crucible can't reach the goal".to_string())
+        );
+        assert_eq!(
+            Solution::render(&Day17, &block, RenderFormat::Text),
+            Some(b"1\n".to_vec())
+        );
+    }
+
+    #[test]
+    fn test_verify_route() {
+        let data = parse("1111\n1111\n").unwrap();
+        let route = |moves: &[(usize, usize, Dir)]| Route {
+            start: Pos::new(0, 0),
+            moves: moves
+                .iter()
+                .map(|&(y, x, dir)| (Pos::new(y, x), Heading::from(dir)))
+                .collect(),
+        };
+
+        let valid = route(&[
+            (0, 1, Dir::Right),
+            (1, 1, Dir::Down),
+            (1, 2, Dir::Right),
+            (1, 3, Dir::Right),
+        ]);
+        assert_eq!(verify_route(&data, &rules(1, 2), &valid), Ok(4));
+        assert_eq!(
+            verify_route(&data, &rules(2, 3), &valid),
+            Err(RouteError::RunTooShort(1))
+        );
+
+        let straight = route(&[
+            (0, 1, Dir::Right),
+            (0, 2, Dir::Right),
+            (0, 3, Dir::Right),
+            (1, 3, Dir::Down),
+        ]);
+        assert_eq!(verify_route(&data, &rules(1, 3), &straight), Ok(4));
+        assert_eq!(
+            verify_route(&data, &rules(1, 2), &straight),
+            Err(RouteError::RunTooLong(2))
+        );
+
+        let reversing = route(&[(0, 1, Dir::Right), (0, 0, Dir::Left)]);
+        assert_eq!(
+            verify_route(&data, &rules(1, 3), &reversing),
+            Err(RouteError::Reversed(1))
+        );
+
+        let jumping = route(&[(0, 2, Dir::Right)]);
+        assert_eq!(
+            verify_route(&data, &rules(1, 3), &jumping),
+            Err(RouteError::NotAStep(0))
+        );
+
+        let short = route(&[(0, 1, Dir::Right)]);
+        assert_eq!(
+            verify_route(&data, &rules(1, 3), &short),
+            Err(RouteError::WrongEnd(Pos::new(0, 1)))
+        );
+    }
+}
@@ -1,8 +1,7 @@
-#![cfg_attr(feature = "bench", feature(test))]
-use advent_of_code_2023::{Cli, Parser};
-use std::fs;
+use crate::parse_util::{parse_number, split_once, strip_prefix, strip_suffix, ParseError};
+use crate::Solution;
 
-struct RawInst<'a> {
+pub struct RawInst<'a> {
     dir: u8,
     dist: i64,
     colour: &'a str,
@@ -14,21 +13,32 @@ struct Inst {
     dist: i64,
 }
 
-fn parse(raw_inp: &str) -> Vec<RawInst> {
-    raw_inp
-        .trim()
-        .lines()
-        .map(|x| {
-            let (dir, rest) = x.split_once(' ').expect("failed split");
-            let (dist, colour) = rest.split_once(' ').expect("failed split");
+fn parse_line(line: &str) -> Result<RawInst<'_>, ParseError> {
+    let (dir, rest) = split_once(line, " ")?;
+    let (dist, colour) = split_once(rest, " ")?;
 
-            RawInst {
-                dir: dir.as_bytes()[0],
-                dist: dist.parse().expect("failed dist parse"),
-                colour,
-            }
-        })
-        .collect()
+    if !matches!(dir, "L" | "R" | "U" | "D") {
+        return Err(ParseError::new(dir, "a direction (L, R, U or D)"));
+    }
+
+    // Colour is a distance in 5 hex digits followed by a direction digit
+    let hex = strip_suffix(strip_prefix(colour, "(#")?, ")")?;
+    if hex.len() != 6 || !hex.bytes().all(|b| b.is_ascii_hexdigit()) || hex.as_bytes()[5] > b'3' {
+        return Err(ParseError::new(
+            hex,
+            "a colour code (5 hex digits and a direction 0-3)",
+        ));
+    }
+
+    Ok(RawInst {
+        dir: dir.as_bytes()[0],
+        dist: parse_number(dist)?,
+        colour,
+    })
+}
+
+fn parse(raw_inp: &str) -> Result<Vec<RawInst<'_>>, ParseError> {
+    raw_inp.trim().lines().map(parse_line).collect()
 }
 
 fn convert_hex(h: &str) -> Inst {
@@ -96,23 +106,41 @@ fn calculate_p2(inst: &[RawInst]) -> i64 {
     dig_and_fill(&actual_instructions)
 }
 
-fn main() {
-    let args = Cli::parse();
+pub struct Day18;
+
+impl Solution for Day18 {
+    type Input<'a> = Vec<RawInst<'a>>;
+    type Answer1 = i64;
+    type Answer2 = i64;
 
-    let inp = fs::read_to_string(args.input).expect("can't open input file");
+    fn parse<'a>(&self, raw_inp: &'a str) -> Result<Self::Input<'a>, ParseError> {
+        parse(raw_inp)
+    }
+
+    fn part1(&self, input: &Self::Input<'_>) -> i64 {
+        calculate_p1(input)
+    }
 
-    let data = parse(&inp);
-    let p1 = calculate_p1(&data);
-    let p2 = calculate_p2(&data);
-    println!("{}\n{}", p1, p2);
+    fn part2(&self, input: &Self::Input<'_>) -> Option<i64> {
+        Some(calculate_p2(input))
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    const EXAMPLE_DATA: &str = include_str!("../../inputs/examples/2023_18");
-    const REAL_DATA: &str = include_str!("../../inputs/real/2023_18");
+    aoc_tests! {
+        real_input: "2023_18";
+
+        p1 (part 1): |inp| calculate_p1(&parse(inp).unwrap()) => {
+            example("2023_18") => 62,
+        }
+
+        p2 (part 2): |inp| calculate_p2(&parse(inp).unwrap()) => {
+            example("2023_18") => 952408144115,
+        }
+    }
 
     #[test]
     fn test_convert_hex() {
@@ -215,42 +243,4 @@ mod tests {
             }
         );
     }
-
-    #[test]
-    fn test_p1_example() {
-        assert_eq!(calculate_p1(&parse(EXAMPLE_DATA)), 62);
-    }
-
-    #[test]
-    fn test_p2_example() {
-        assert_eq!(calculate_p2(&parse(EXAMPLE_DATA)), 952408144115);
-    }
-
-    #[test]
-    fn test_p1_real() {
-        assert_eq!(calculate_p1(&parse(REAL_DATA)), 47527);
-    }
-
-    #[test]
-    fn test_p2_real() {
-        assert_eq!(calculate_p2(&parse(REAL_DATA)), 52240187443190);
-    }
-
-    #[cfg(feature = "bench")]
-    mod benches {
-        extern crate test;
-        use test::{black_box, Bencher};
-
-        use super::*;
-
-        #[bench]
-        fn bench(b: &mut Bencher) {
-            b.iter(|| {
-                let data = parse(black_box(REAL_DATA));
-                let p1 = calculate_p1(&data);
-                let p2 = calculate_p2(&data);
-                (p1, p2)
-            });
-        }
-    }
 }
@@ -0,0 +1,939 @@
+use crate::parse_util::{parse_number, split_once, strip_prefix, strip_suffix, ParseError};
+use crate::Solution;
+use ahash::AHashMap;
+use std::collections::BTreeSet;
+use std::fmt;
+use std::ops::Range;
+
+const MIN_RATING: i64 = 1;
+const MAX_RATING: i64 = 4000;
+
+/// Index of a workflow, in the order they appear in the input.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+struct WorkflowId(usize);
+
+/// Index of a rating dimension, in the order they appear in each part.
+type DimId = usize;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Dest {
+    Accept,
+    Reject,
+    Workflow(WorkflowId),
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Op {
+    Lt,
+    Gt,
+}
+
+#[derive(Debug)]
+struct Rule {
+    dim: DimId,
+    op: Op,
+    n: i64,
+    to: Dest,
+}
+
+#[derive(Debug)]
+struct Workflow {
+    rules: Vec<Rule>,
+    // Where parts go if no rule matches
+    otherwise: Dest,
+}
+
+impl Rule {
+    // Splits into the ratings which match this rule and those which don't
+    fn split(&self, range: HypercubeRange) -> (Option<HypercubeRange>, Option<HypercubeRange>) {
+        match self.op {
+            Op::Lt => range.split_at(self.dim, self.n),
+            Op::Gt => {
+                let (below, above) = range.split_at(self.dim, self.n + 1);
+                (above, below)
+            }
+        }
+    }
+}
+
+// Sends a range through `rules`, returning the non-empty pieces matched by each
+// rule, with `None` for the pieces which match no rule
+fn route(rules: &[Rule], range: HypercubeRange) -> Vec<(Option<usize>, HypercubeRange)> {
+    let mut result = vec![];
+    let mut rest = Some(range);
+
+    for (idx, rule) in rules.iter().enumerate() {
+        let Some(range) = rest else {
+            break;
+        };
+        let (matched, unmatched) = rule.split(range);
+        result.extend(matched.map(|r| (Some(idx), r)));
+        rest = unmatched;
+    }
+
+    result.extend(rest.map(|r| (None, r)));
+    result
+}
+
+// Follows every range of ratings from `start`, returning the workflows of a
+// loop which some parts would go around forever, if there is one. See
+// `Data::lint` for every such loop.
+fn find_cycle(workflows: &[Workflow], start: WorkflowId, dims: usize) -> Option<Vec<WorkflowId>> {
+    let mut stack = vec![(start, HypercubeRange::full(dims), vec![])];
+
+    while let Some((id, range, mut path)) = stack.pop() {
+        // Parts coming back to a workflow will take the same route again
+        if let Some(pos) = path.iter().position(|&p| p == id) {
+            return Some(path[pos..].to_vec());
+        }
+        path.push(id);
+
+        let workflow = &workflows[id.0];
+        for (idx, piece) in route(&workflow.rules, range) {
+            let dest = idx.map_or(workflow.otherwise, |idx| workflow.rules[idx].to);
+            if let Dest::Workflow(next) = dest {
+                stack.push((next, piece, path.clone()));
+            }
+        }
+    }
+
+    None
+}
+
+#[derive(Debug)]
+struct Item {
+    ratings: Vec<i64>,
+}
+
+/// Half-open range of ratings in each dimension.
+#[derive(Clone, Debug, Eq, PartialEq)]
+struct HypercubeRange {
+    ranges: Vec<(i64, i64)>,
+}
+
+impl HypercubeRange {
+    fn full(dims: usize) -> HypercubeRange {
+        HypercubeRange {
+            ranges: vec![(MIN_RATING, MAX_RATING + 1); dims],
+        }
+    }
+
+    fn permutations(&self) -> i64 {
+        self.ranges.iter().map(|r| r.1 - r.0).product()
+    }
+
+    // Splits into ratings below `n` in `dim` and the rest, either of which may be empty
+    fn split_at(self, dim: DimId, n: i64) -> (Option<HypercubeRange>, Option<HypercubeRange>) {
+        let (lo, hi) = self.ranges[dim];
+        if n <= lo {
+            (None, Some(self))
+        } else if n >= hi {
+            (Some(self), None)
+        } else {
+            let mut below = self.clone();
+            let mut above = self;
+            below.ranges[dim].1 = n;
+            above.ranges[dim].0 = n;
+            (Some(below), Some(above))
+        }
+    }
+}
+
+type NodeId = usize;
+
+const ACCEPT: NodeId = 0;
+const REJECT: NodeId = 1;
+
+#[derive(Clone, Copy, Debug)]
+enum Node {
+    Accept,
+    Reject,
+    // A workflow without any conditions
+    Goto(NodeId),
+    // Goes to `lt` if the rating in `dim` is less than `n`, or `ge` otherwise
+    Split {
+        dim: DimId,
+        n: i64,
+        lt: NodeId,
+        ge: NodeId,
+    },
+}
+
+/// Every workflow compiled into one graph of comparisons, with one node per
+/// rule. Workflows are jumped straight into rather than looked up by name.
+///
+/// Walking every range of ratings through the graph never finishes if it has a
+/// loop which some parts can reach, so check for one with `find_cycle` first.
+#[derive(Debug)]
+struct DecisionTree {
+    nodes: Vec<Node>,
+    root: NodeId,
+    // First node of each workflow, and the workflow each node belongs to
+    roots: Vec<NodeId>,
+    owners: Vec<Option<WorkflowId>>,
+}
+
+impl DecisionTree {
+    fn compile(workflows: &[Workflow], start: WorkflowId) -> DecisionTree {
+        // Each workflow's nodes are laid out consecutively, after accept and reject
+        let mut roots = Vec::with_capacity(workflows.len());
+        let mut next = 2;
+        for workflow in workflows {
+            roots.push(next);
+            next += workflow.rules.len().max(1);
+        }
+
+        let target = |dest| match dest {
+            Dest::Accept => ACCEPT,
+            Dest::Reject => REJECT,
+            Dest::Workflow(id) => roots[id.0],
+        };
+
+        let mut nodes = Vec::with_capacity(next);
+        nodes.push(Node::Accept);
+        nodes.push(Node::Reject);
+
+        let mut owners = vec![None, None];
+
+        for (id, workflow) in workflows.iter().enumerate() {
+            owners.resize(
+                nodes.len() + workflow.rules.len().max(1),
+                Some(WorkflowId(id)),
+            );
+
+            if workflow.rules.is_empty() {
+                nodes.push(Node::Goto(target(workflow.otherwise)));
+            }
+
+            for (idx, rule) in workflow.rules.iter().enumerate() {
+                let no_match = if idx + 1 < workflow.rules.len() {
+                    nodes.len() + 1
+                } else {
+                    target(workflow.otherwise)
+                };
+
+                // Normalise to a single comparison: x > n is the same as !(x < n + 1)
+                nodes.push(match rule.op {
+                    Op::Lt => Node::Split {
+                        dim: rule.dim,
+                        n: rule.n,
+                        lt: target(rule.to),
+                        ge: no_match,
+                    },
+                    Op::Gt => Node::Split {
+                        dim: rule.dim,
+                        n: rule.n + 1,
+                        lt: no_match,
+                        ge: target(rule.to),
+                    },
+                });
+            }
+        }
+
+        DecisionTree {
+            nodes,
+            root: roots[start.0],
+            roots,
+            owners,
+        }
+    }
+
+    // The workflow being entered by moving to `node`, if any
+    fn entered_workflow(&self, node: NodeId) -> Option<WorkflowId> {
+        self.owners[node].filter(|id| self.roots[id.0] == node)
+    }
+
+    // Whether a part is accepted, or `None` if it goes round a loop forever
+    fn accepts(&self, ratings: &[i64]) -> Option<bool> {
+        let mut node = self.root;
+        // A part which doesn't loop visits each node at most once
+        for _ in 0..self.nodes.len() {
+            match self.nodes[node] {
+                Node::Accept => return Some(true),
+                Node::Reject => return Some(false),
+                Node::Goto(next) => node = next,
+                Node::Split { dim, n, lt, ge } => {
+                    node = if ratings[dim] < n { lt } else { ge };
+                }
+            }
+        }
+        None
+    }
+
+    fn accepted_permutations(&self, dims: usize) -> i64 {
+        let mut result = 0;
+        let mut queue = vec![(self.root, HypercubeRange::full(dims))];
+
+        while let Some((node, range)) = queue.pop() {
+            match self.nodes[node] {
+                Node::Accept => result += range.permutations(),
+                Node::Reject => {}
+                Node::Goto(next) => queue.push((next, range)),
+                Node::Split { dim, n, lt, ge } => {
+                    let (below, above) = range.split_at(dim, n);
+                    queue.extend(below.map(|r| (lt, r)));
+                    queue.extend(above.map(|r| (ge, r)));
+                }
+            }
+        }
+
+        result
+    }
+
+    // Like `accepted_permutations`, but keeping each accepted range and the
+    // workflows it passed through
+    fn accepted_ranges(&self, dims: usize) -> Vec<(HypercubeRange, Vec<WorkflowId>)> {
+        let mut result = vec![];
+        let start_path = self.entered_workflow(self.root).into_iter().collect();
+        let mut queue = vec![(self.root, HypercubeRange::full(dims), start_path)];
+
+        let follow = |next: NodeId, path: &Vec<WorkflowId>| {
+            let mut path = path.clone();
+            path.extend(self.entered_workflow(next));
+            path
+        };
+
+        while let Some((node, range, path)) = queue.pop() {
+            match self.nodes[node] {
+                Node::Accept => result.push((range, path)),
+                Node::Reject => {}
+                Node::Goto(next) => queue.push((next, range, follow(next, &path))),
+                Node::Split { dim, n, lt, ge } => {
+                    let (below, above) = range.split_at(dim, n);
+                    if let Some(below) = below {
+                        queue.push((lt, below, follow(lt, &path)));
+                    }
+                    if let Some(above) = above {
+                        queue.push((ge, above, follow(ge, &path)));
+                    }
+                }
+            }
+        }
+
+        result
+    }
+}
+
+/// A box of ratings which is accepted, with a half-open range for each
+/// dimension, and the workflows which lead to it.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AcceptedRegion<'a> {
+    pub ranges: Vec<Range<i64>>,
+    pub path: Vec<&'a str>,
+}
+
+impl AcceptedRegion<'_> {
+    pub fn contains(&self, ratings: &[i64]) -> bool {
+        self.ranges.iter().zip(ratings).all(|(r, v)| r.contains(v))
+    }
+
+    pub fn permutations(&self) -> i64 {
+        self.ranges.iter().map(|r| r.end - r.start).product()
+    }
+}
+
+/// Every accepted combination of ratings, as disjoint boxes.
+#[derive(Clone, Debug)]
+pub struct AcceptedRegions<'a> {
+    pub dims: Vec<&'a str>,
+    pub regions: Vec<AcceptedRegion<'a>>,
+}
+
+impl AcceptedRegions<'_> {
+    /// The region containing `ratings`, if they're accepted.
+    pub fn find(&self, ratings: &[i64]) -> Option<&AcceptedRegion<'_>> {
+        self.regions.iter().find(|region| region.contains(ratings))
+    }
+
+    pub fn permutations(&self) -> i64 {
+        self.regions.iter().map(|r| r.permutations()).sum()
+    }
+}
+
+impl fmt::Display for AcceptedRegions<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for region in &self.regions {
+            let ranges = self
+                .dims
+                .iter()
+                .zip(&region.ranges)
+                .map(|(dim, r)| format!("{}={}..{}", dim, r.start, r.end))
+                .collect::<Vec<_>>();
+            writeln!(f, "{}: {}", region.path.join(" -> "), ranges.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+pub struct Data<'a> {
+    // Names of each dimension and workflow, indexed by their IDs
+    dims: Vec<&'a str>,
+    workflow_names: Vec<&'a str>,
+    workflows: Vec<Workflow>,
+    start: WorkflowId,
+    tree: DecisionTree,
+    items: Vec<Item>,
+}
+
+/// A rule within a workflow, or its final unconditional rule if `index` is
+/// `None`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RuleRef<'a> {
+    pub workflow: &'a str,
+    pub index: Option<usize>,
+    pub text: String,
+}
+
+impl fmt::Display for RuleRef<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.index {
+            Some(idx) => write!(f, "{} rule {} ({})", self.workflow, idx + 1, self.text),
+            None => write!(f, "{} fallback ({})", self.workflow, self.text),
+        }
+    }
+}
+
+/// Problems found by following every range of ratings through the workflows.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct LintReport<'a> {
+    /// Workflows which no part can reach from `in`.
+    pub unreachable_workflows: Vec<&'a str>,
+    /// Rules, in reachable workflows, which no part reaching them matches.
+    pub dead_rules: Vec<RuleRef<'a>>,
+    /// Rules which send every part they match to where it would have gone anyway.
+    pub redundant_rules: Vec<RuleRef<'a>>,
+    /// Loops which some parts go around forever, starting from the first
+    /// workflow in each loop.
+    pub cycles: Vec<Vec<&'a str>>,
+}
+
+impl LintReport<'_> {
+    pub fn is_clean(&self) -> bool {
+        *self == LintReport::default()
+    }
+}
+
+impl fmt::Display for LintReport<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for name in &self.unreachable_workflows {
+            writeln!(f, "unreachable workflow: {}", name)?;
+        }
+        for rule in &self.dead_rules {
+            writeln!(f, "dead rule: {}", rule)?;
+        }
+        for rule in &self.redundant_rules {
+            writeln!(f, "redundant rule: {}", rule)?;
+        }
+        for cycle in &self.cycles {
+            writeln!(f, "cycle: {} -> {}", cycle.join(" -> "), cycle[0])?;
+        }
+        Ok(())
+    }
+}
+
+impl<'a> Data<'a> {
+    // Fails if some parts go round a loop forever, so have no final destination
+    fn check_loops(&self) -> Result<(), String> {
+        match find_cycle(&self.workflows, self.start, self.dims.len()) {
+            Some(cycle) => {
+                let names = cycle
+                    .iter()
+                    .chain(&cycle[..1])
+                    .map(|id| self.workflow_names[id.0])
+                    .collect::<Vec<_>>();
+                Err(format!("some parts loop forever: {}", names.join(" -> ")))
+            }
+            None => Ok(()),
+        }
+    }
+
+    /// Every accepted box of ratings, or an error if some parts loop forever.
+    pub fn accepted_regions(&self) -> Result<AcceptedRegions<'a>, String> {
+        self.check_loops()?;
+
+        let regions = self
+            .tree
+            .accepted_ranges(self.dims.len())
+            .into_iter()
+            .map(|(range, path)| AcceptedRegion {
+                ranges: range.ranges.iter().map(|&(lo, hi)| lo..hi).collect(),
+                path: path.iter().map(|id| self.workflow_names[id.0]).collect(),
+            })
+            .collect();
+
+        Ok(AcceptedRegions {
+            dims: self.dims.clone(),
+            regions,
+        })
+    }
+
+    /// Ratings of every part in the parts list.
+    pub fn parts(&self) -> impl Iterator<Item = &[i64]> {
+        self.items.iter().map(|item| item.ratings.as_slice())
+    }
+
+    /// Parses a part written as in the parts list, e.g. `{x=787,m=2655,a=1222,s=2876}`.
+    pub fn parse_part(&self, part: &str) -> Result<Vec<i64>, ParseError> {
+        let ratings = parse_item(part)?;
+
+        if !ratings
+            .iter()
+            .map(|&(name, _)| name)
+            .eq(self.dims.iter().copied())
+        {
+            return Err(ParseError::new(
+                part,
+                format!("ratings {}, in that order", self.dims.join(", ")),
+            ));
+        }
+        Ok(ratings.into_iter().map(|(_, value)| value).collect())
+    }
+
+    /// Runs a part through the workflows, returning `None` if it loops forever.
+    pub fn accepts(&self, ratings: &[i64]) -> Option<bool> {
+        self.tree.accepts(ratings)
+    }
+
+    fn dest_name(&self, dest: Dest) -> &'a str {
+        match dest {
+            Dest::Accept => "A",
+            Dest::Reject => "R",
+            Dest::Workflow(id) => self.workflow_names[id.0],
+        }
+    }
+
+    fn rule_ref(&self, id: WorkflowId, index: Option<usize>) -> RuleRef<'a> {
+        let workflow = &self.workflows[id.0];
+        let text = match index {
+            Some(idx) => {
+                let rule = &workflow.rules[idx];
+                let op = if rule.op == Op::Lt { '<' } else { '>' };
+                let to = self.dest_name(rule.to);
+                format!("{}{}{}:{}", self.dims[rule.dim], op, rule.n, to)
+            }
+            None => self.dest_name(workflow.otherwise).to_string(),
+        };
+
+        RuleRef {
+            workflow: self.workflow_names[id.0],
+            index,
+            text,
+        }
+    }
+
+    /// Follows every range of ratings from `in`, like part 2, recording which
+    /// workflows and rules are ever used.
+    pub fn lint(&self) -> LintReport<'a> {
+        let dest = |workflow: &Workflow, idx: Option<usize>| {
+            idx.map_or(workflow.otherwise, |idx| workflow.rules[idx].to)
+        };
+
+        let mut reached = vec![false; self.workflows.len()];
+        // Indexed by rule, with the final unconditional rule last
+        let mut fired = self
+            .workflows
+            .iter()
+            .map(|w| vec![false; w.rules.len() + 1])
+            .collect::<Vec<_>>();
+        let mut redundant = self
+            .workflows
+            .iter()
+            .map(|w| vec![true; w.rules.len()])
+            .collect::<Vec<_>>();
+        let mut cycles = BTreeSet::new();
+
+        let mut stack = vec![(self.start, HypercubeRange::full(self.dims.len()), vec![])];
+
+        while let Some((id, range, mut path)) = stack.pop() {
+            // Parts coming back to a workflow will take the same route again, forever
+            if let Some(pos) = path.iter().position(|&p| p == id) {
+                let mut cycle: Vec<WorkflowId> = path[pos..].to_vec();
+                let first = (0..cycle.len()).min_by_key(|&i| cycle[i]).unwrap_or(0);
+                cycle.rotate_left(first);
+                cycles.insert(cycle);
+                continue;
+            }
+
+            reached[id.0] = true;
+            path.push(id);
+
+            let workflow = &self.workflows[id.0];
+            for (idx, piece) in route(&workflow.rules, range) {
+                fired[id.0][idx.unwrap_or(workflow.rules.len())] = true;
+
+                // Would these parts have ended up in the same place without this rule?
+                if let Some(idx) = idx {
+                    let rest = &workflow.rules[idx + 1..];
+                    let same_dest = route(rest, piece.clone()).into_iter().all(|(other, _)| {
+                        dest(workflow, other.map(|o| o + idx + 1)) == workflow.rules[idx].to
+                    });
+                    redundant[id.0][idx] &= same_dest;
+                }
+
+                if let Dest::Workflow(next) = dest(workflow, idx) {
+                    stack.push((next, piece, path.clone()));
+                }
+            }
+        }
+
+        let mut report = LintReport::default();
+
+        for (idx, workflow) in self.workflows.iter().enumerate() {
+            let id = WorkflowId(idx);
+            if !reached[idx] {
+                report.unreachable_workflows.push(self.workflow_names[idx]);
+                continue;
+            }
+
+            for rule in 0..=workflow.rules.len() {
+                let index = (rule < workflow.rules.len()).then_some(rule);
+                if !fired[idx][rule] {
+                    report.dead_rules.push(self.rule_ref(id, index));
+                } else if index.is_some() && redundant[idx][rule] {
+                    report.redundant_rules.push(self.rule_ref(id, index));
+                }
+            }
+        }
+
+        report.cycles = cycles
+            .into_iter()
+            .map(|cycle| cycle.iter().map(|id| self.workflow_names[id.0]).collect())
+            .collect();
+
+        report
+    }
+}
+
+fn intern<'a>(names: &mut Vec<&'a str>, name: &'a str) -> usize {
+    names.iter().position(|&n| n == name).unwrap_or_else(|| {
+        names.push(name);
+        names.len() - 1
+    })
+}
+
+// Dims are fixed by the parts list if it has any parts, otherwise taken from the rules
+fn parse_dim<'a>(cond: &'a str, dims: &mut Vec<&'a str>, fixed: bool) -> Result<DimId, ParseError> {
+    let end = cond.find(['<', '>']).unwrap_or(cond.len());
+    let name = &cond[..end];
+
+    match dims.iter().position(|&d| d == name) {
+        Some(dim) => Ok(dim),
+        None if !fixed && !name.is_empty() => Ok(intern(dims, name)),
+        None => Err(ParseError::new(
+            cond,
+            format!("a rating from the parts list ({})", dims.join(", ")),
+        )),
+    }
+}
+
+fn parse_dest(dest: &str, workflow_ids: &AHashMap<&str, WorkflowId>) -> Result<Dest, ParseError> {
+    match dest {
+        "A" => Ok(Dest::Accept),
+        "R" => Ok(Dest::Reject),
+        _ => workflow_ids
+            .get(dest)
+            .map(|&id| Dest::Workflow(id))
+            .ok_or_else(|| ParseError::new(dest, "a known workflow, 'A' or 'R'")),
+    }
+}
+
+fn parse_rule<'a>(
+    cond: &'a str,
+    dest: &'a str,
+    dims: &mut Vec<&'a str>,
+    fixed_dims: bool,
+    workflow_ids: &AHashMap<&str, WorkflowId>,
+) -> Result<Rule, ParseError> {
+    let dim = parse_dim(cond, dims, fixed_dims)?;
+    let cond = &cond[dims[dim].len()..];
+
+    let op = match cond.get(0..1) {
+        Some("<") => Op::Lt,
+        Some(">") => Op::Gt,
+        _ => return Err(ParseError::new(cond, "'<' or '>'")),
+    };
+
+    let n = parse_number(&cond[1..])?;
+    // Matching x > n is done as x >= n + 1
+    if op == Op::Gt && n == i64::MAX {
+        return Err(ParseError::new(
+            &cond[1..],
+            format!("a number below {}", i64::MAX),
+        ));
+    }
+
+    Ok(Rule {
+        dim,
+        op,
+        n,
+        to: parse_dest(dest, workflow_ids)?,
+    })
+}
+
+fn parse_workflow<'a>(
+    line: &'a str,
+    dims: &mut Vec<&'a str>,
+    fixed_dims: bool,
+    workflow_ids: &AHashMap<&str, WorkflowId>,
+) -> Result<Workflow, ParseError> {
+    let (_, rest) = split_once(line, "{")?;
+    let rest = strip_suffix(rest, "}")?;
+
+    let (conditional, otherwise) = rest.rsplit_once(',').unwrap_or(("", rest));
+    if otherwise.contains(':') {
+        return Err(ParseError::new(
+            otherwise,
+            "a final rule without a condition",
+        ));
+    }
+
+    let rules = conditional
+        .split(',')
+        .filter(|rule| !rule.is_empty())
+        .map(|rule| {
+            let (cond, dest) = split_once(rule, ":")?;
+            parse_rule(cond, dest, dims, fixed_dims, workflow_ids)
+        })
+        .collect::<Result<_, _>>()?;
+
+    Ok(Workflow {
+        rules,
+        otherwise: parse_dest(otherwise, workflow_ids)?,
+    })
+}
+
+fn parse_item(line: &str) -> Result<Vec<(&str, i64)>, ParseError> {
+    let line = strip_prefix(line, "{")?;
+    let line = strip_suffix(line, "}")?;
+
+    line.split(',')
+        .map(|l| {
+            let (name, value) = split_once(l, "=")?;
+            Ok((name, parse_number(value)?))
+        })
+        .collect()
+}
+
+pub fn parse(raw_inp: &str) -> Result<Data<'_>, ParseError> {
+    // The parts list may be left out when only part 2 is wanted
+    let raw_inp = raw_inp.trim();
+    let (workflows_str, items_str) = raw_inp.split_once("\n\n").unwrap_or((raw_inp, ""));
+
+    let raw_items = items_str
+        .lines()
+        .map(|line| parse_item(line).map(|ratings| (line, ratings)))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut dims = raw_items
+        .first()
+        .map(|(_, ratings)| ratings.iter().map(|&(name, _)| name).collect::<Vec<_>>())
+        .unwrap_or_default();
+    let fixed_dims = !raw_items.is_empty();
+
+    // Every part must have the same, distinct, ratings in the same order
+    let distinct = (1..dims.len()).all(|i| !dims[..i].contains(&dims[i]));
+    let items = raw_items
+        .into_iter()
+        .map(|(line, ratings)| {
+            if !distinct
+                || !ratings
+                    .iter()
+                    .map(|&(name, _)| name)
+                    .eq(dims.iter().copied())
+            {
+                return Err(ParseError::new(
+                    line,
+                    format!("distinct ratings {}, in that order", dims.join(", ")),
+                ));
+            }
+            Ok(Item {
+                ratings: ratings.into_iter().map(|(_, value)| value).collect(),
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    // Intern names first, so rules can refer to workflows defined later
+    let mut workflow_names = vec![];
+    let mut workflow_ids = AHashMap::default();
+    for (idx, line) in workflows_str.lines().enumerate() {
+        let (name, _) = split_once(line, "{")?;
+        if workflow_ids.insert(name, WorkflowId(idx)).is_some() {
+            return Err(ParseError::new(name, "a workflow name not already used"));
+        }
+        workflow_names.push(name);
+    }
+
+    let start = *workflow_ids
+        .get("in")
+        .ok_or_else(|| ParseError::new(workflows_str, "an 'in' workflow"))?;
+
+    let workflows = workflows_str
+        .lines()
+        .map(|line| parse_workflow(line, &mut dims, fixed_dims, &workflow_ids))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let tree = DecisionTree::compile(&workflows, start);
+
+    Ok(Data {
+        dims,
+        workflow_names,
+        workflows,
+        start,
+        tree,
+        items,
+    })
+}
+
+fn calculate_p1(data: &Data) -> Result<i64, String> {
+    let mut result = 0;
+    for (idx, item) in data.items.iter().enumerate() {
+        let accepted = data
+            .tree
+            .accepts(&item.ratings)
+            .ok_or_else(|| format!("part {} in the list loops forever", idx + 1))?;
+        if accepted {
+            result += item.ratings.iter().sum::<i64>();
+        }
+    }
+    Ok(result)
+}
+
+fn calculate_p2(data: &Data) -> Result<i64, String> {
+    data.check_loops()?;
+    Ok(data.tree.accepted_permutations(data.dims.len()))
+}
+
+pub struct Day19;
+
+impl Solution for Day19 {
+    type Input<'a> = Data<'a>;
+    // Either answer fails if some parts loop forever
+    type Answer1 = Result<i64, String>;
+    type Answer2 = Result<i64, String>;
+
+    fn parse<'a>(&self, raw_inp: &'a str) -> Result<Self::Input<'a>, ParseError> {
+        parse(raw_inp)
+    }
+
+    fn part1(&self, input: &Self::Input<'_>) -> Result<i64, String> {
+        calculate_p1(input)
+    }
+
+    fn part2(&self, input: &Self::Input<'_>) -> Option<Result<i64, String>> {
+        Some(calculate_p2(input))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    aoc_tests! {
+        real_input: "2023_19";
+
+        p1 (part 1): |inp| calculate_p1(&parse(inp).unwrap()).unwrap() => {
+            example("2023_19") => 19114,
+        }
+
+        p2 (part 2): |inp| calculate_p2(&parse(inp).unwrap()).unwrap() => {
+            example("2023_19") => 167409079868000,
+        }
+    }
+
+    #[test]
+    fn test_other_dimensions() {
+        let data =
+            parse("in{a<10:A,b>5:foo,R}\nfoo{A}\n\n{a=12,b=6}\n{a=3,b=1}\n{a=12,b=2}").unwrap();
+        assert_eq!(data.dims, vec!["a", "b"]);
+        assert_eq!(calculate_p1(&data), Ok(18 + 4));
+        // a < 10, or a >= 10 and b > 5
+        assert_eq!(calculate_p2(&data), Ok(9 * 4000 + 3991 * 3995));
+
+        // Without parts, dims come from the rules
+        let data = parse("in{y<2001:A,R}\n\n").unwrap();
+        assert_eq!(data.dims, vec!["y"]);
+        assert_eq!(calculate_p2(&data), Ok(2000));
+
+        assert!(parse("in{z<5:A,R}\n\n{x=1}").is_err());
+        assert!(parse("in{x>9223372036854775806:A,R}").is_ok());
+        assert!(parse("in{x>9223372036854775807:A,R}").is_err());
+        assert!(parse("in{x<-9223372036854775808:A,R}").is_ok());
+        assert!(parse("in{x<5:A,R}\n\n{x=1}\n{m=1}").is_err());
+    }
+
+    #[test]
+    fn test_accepted_regions() {
+        let data = parse(fixture!("2023_19")).unwrap();
+        let regions = data.accepted_regions().unwrap();
+
+        // Agrees with both parts
+        assert_eq!(Ok(regions.permutations()), calculate_p2(&data));
+        for part in data.parts() {
+            assert_eq!(Some(regions.find(part).is_some()), data.accepts(part));
+        }
+
+        let part = data.parse_part("{x=787,m=2655,a=1222,s=2876}").unwrap();
+        assert_eq!(
+            regions.find(&part).unwrap().path,
+            vec!["in", "qqz", "qs", "lnx"]
+        );
+
+        assert!(data.parse_part("{x=1,m=2,a=3}").is_err());
+    }
+
+    #[test]
+    fn test_lint() {
+        let data = parse(fixture!("2023_19")).unwrap();
+        let report = data.lint();
+        assert_eq!(
+            report
+                .redundant_rules
+                .iter()
+                .map(|r| r.to_string())
+                .collect::<Vec<_>>(),
+            vec!["lnx rule 1 (m>1548:A)", "gd rule 1 (a>3333:R)"]
+        );
+        assert!(report.unreachable_workflows.is_empty());
+        assert!(report.dead_rules.is_empty());
+        assert!(report.cycles.is_empty());
+
+        let data = parse("in{x<10:a,x<5:b,R}\na{x>20:c,A}\nb{A}\nc{A}\nd{e}\ne{d}").unwrap();
+        let report = data.lint();
+        assert_eq!(report.unreachable_workflows, vec!["b", "c", "d", "e"]);
+        assert_eq!(
+            report
+                .dead_rules
+                .iter()
+                .map(|r| r.to_string())
+                .collect::<Vec<_>>(),
+            vec!["in rule 2 (x<5:b)", "a rule 1 (x>20:c)"]
+        );
+        // Loops which no part can reach are harmless
+        assert!(report.cycles.is_empty());
+        assert!(!report.is_clean());
+    }
+
+    #[test]
+    fn test_cycles() {
+        // Parts with x < 5 and m > 7 loop forever, so there's no part 2
+        let data = parse("in{x<10:a,R}\na{x<5:b,A}\nb{m>7:in,R}\n\n{x=7,m=1}\n{x=1,m=1}").unwrap();
+        let report = data.lint();
+        assert_eq!(report.cycles, vec![vec!["in", "a", "b"]]);
+        assert!(report.to_string().contains("cycle: in -> a -> b -> in"));
+        assert_eq!(
+            calculate_p2(&data),
+            Err("some parts loop forever: in -> a -> b -> in".to_string())
+        );
+        assert!(data.accepted_regions().is_err());
+
+        // Part 1 only fails if one of the parts in the list loops
+        assert_eq!(calculate_p1(&data), Ok(7 + 1));
+        assert_eq!(data.accepts(&[1, 8]), None);
+        let data = parse("in{x<10:a,R}\na{x<5:b,A}\nb{m>7:in,R}\n\n{x=1,m=8}").unwrap();
+        assert_eq!(
+            calculate_p1(&data),
+            Err("part 1 in the list loops forever".to_string())
+        );
+    }
+}
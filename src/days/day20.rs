@@ -0,0 +1,924 @@
+use crate::graphviz::DotGraph;
+use crate::parse_util::{split_once, ParseError};
+use crate::Solution;
+use ahash::AHashMap;
+use bitvec::prelude::*;
+use num::Integer;
+use std::collections::VecDeque;
+use std::fmt;
+
+#[derive(Debug, Eq, PartialEq, Copy, Clone, Hash)]
+pub enum ModuleType {
+    /// Pressing it sends a low pulse to the broadcaster.
+    Button,
+    Broadcaster,
+    Flipflop,
+    Conjunction,
+    /// Only ever receives pulses, like `rx`.
+    Output,
+}
+
+#[derive(Debug, Eq, PartialEq, Copy, Clone, Hash)]
+pub enum Pulse {
+    Low,
+    High,
+}
+
+impl fmt::Display for Pulse {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Pulse::Low => write!(f, "low"),
+            Pulse::High => write!(f, "high"),
+        }
+    }
+}
+
+/// Index of a module within its `Network`.
+pub type ModuleId = usize;
+
+#[derive(Debug, Eq, PartialEq)]
+struct Module<'a> {
+    name: &'a str,
+    typ: ModuleType,
+    outputs: Vec<ModuleId>,
+    inputs: Vec<ModuleId>,
+}
+
+/// Modules with their names interned, so they can be looked up by index.
+#[derive(Debug, Eq, PartialEq)]
+pub struct Network<'a> {
+    modules: Vec<Module<'a>>,
+    ids: AHashMap<&'a str, ModuleId>,
+}
+
+const BUTTON: &str = "button";
+const BROADCASTER: &str = "broadcaster";
+
+impl<'a> Network<'a> {
+    fn intern(&mut self, name: &'a str) -> ModuleId {
+        *self.ids.entry(name).or_insert_with(|| {
+            self.modules.push(Module {
+                name,
+                typ: ModuleType::Output,
+                outputs: vec![],
+                inputs: vec![],
+            });
+            self.modules.len() - 1
+        })
+    }
+
+    pub fn len(&self) -> usize {
+        self.modules.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.modules.is_empty()
+    }
+
+    pub fn id(&self, name: &str) -> Option<ModuleId> {
+        self.ids.get(name).copied()
+    }
+
+    pub fn name(&self, id: ModuleId) -> &'a str {
+        self.modules[id].name
+    }
+
+    pub fn typ(&self, id: ModuleId) -> ModuleType {
+        self.modules[id].typ
+    }
+
+    pub fn outputs(&self, id: ModuleId) -> &[ModuleId] {
+        &self.modules[id].outputs
+    }
+
+    pub fn inputs(&self, id: ModuleId) -> &[ModuleId] {
+        &self.modules[id].inputs
+    }
+
+    pub fn button(&self) -> ModuleId {
+        self.ids[BUTTON]
+    }
+
+    pub fn broadcaster(&self) -> ModuleId {
+        self.ids[BROADCASTER]
+    }
+
+    /// Every module's ID, in the order they first appear in the input.
+    pub fn ids(&self) -> impl Iterator<Item = ModuleId> {
+        0..self.modules.len()
+    }
+}
+
+fn parse_line(line: &str) -> Result<(&str, ModuleType, Vec<&str>), ParseError> {
+    let (src, dest) = split_once(line, " -> ")?;
+
+    let (typ, name) = match src.get(0..1) {
+        _ if src == BROADCASTER => (ModuleType::Broadcaster, BROADCASTER),
+        Some("%") => (ModuleType::Flipflop, &src[1..]),
+        Some("&") => (ModuleType::Conjunction, &src[1..]),
+        _ => {
+            return Err(ParseError::new(
+                src,
+                "a module ('broadcaster', '%name' or '&name')",
+            ))
+        }
+    };
+
+    let outputs = dest.split(',').map(|s| s.trim()).collect::<Vec<_>>();
+
+    Ok((name, typ, outputs))
+}
+
+pub fn parse(raw_inp: &str) -> Result<Network<'_>, ParseError> {
+    let mut network = Network {
+        modules: vec![],
+        ids: AHashMap::default(),
+    };
+
+    let button = network.intern(BUTTON);
+    network.modules[button].typ = ModuleType::Button;
+
+    for line in raw_inp.trim().lines() {
+        let (name, typ, outputs) = parse_line(line)?;
+        if name == BUTTON {
+            return Err(ParseError::new(line, "a module other than 'button'"));
+        }
+
+        let id = network.intern(name);
+        network.modules[id].typ = typ;
+        network.modules[id].outputs = outputs.into_iter().map(|o| network.intern(o)).collect();
+    }
+
+    let Some(broadcaster) = network.id(BROADCASTER) else {
+        return Err(ParseError::new(raw_inp.trim(), "a 'broadcaster' module"));
+    };
+    network.modules[button].outputs = vec![broadcaster];
+
+    for id in network.ids() {
+        for idx in 0..network.modules[id].outputs.len() {
+            let output = network.modules[id].outputs[idx];
+            network.modules[output].inputs.push(id);
+        }
+    }
+
+    Ok(network)
+}
+
+/// Pulses sent during one press of the button, including the button's own.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct PressStats {
+    pub low: usize,
+    pub high: usize,
+    /// Flip-flops which changed state.
+    pub flips: usize,
+}
+
+impl std::ops::AddAssign for PressStats {
+    fn add_assign(&mut self, other: PressStats) {
+        self.low += other.low;
+        self.high += other.high;
+        self.flips += other.flips;
+    }
+}
+
+/// A single pulse, sent during button press number `press` (counting from 1).
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct PulseEvent {
+    pub press: usize,
+    pub src: ModuleId,
+    pub dest: ModuleId,
+    pub pulse: Pulse,
+}
+
+/// Receives every pulse a `Simulator` sends, in the order they're sent.
+pub trait TraceSink {
+    fn record(&mut self, event: &PulseEvent);
+}
+
+impl<F: FnMut(&PulseEvent)> TraceSink for F {
+    fn record(&mut self, event: &PulseEvent) {
+        self(event)
+    }
+}
+
+/// Discards pulses, for presses which don't need tracing.
+pub struct NoTrace;
+
+impl TraceSink for NoTrace {
+    fn record(&mut self, _event: &PulseEvent) {}
+}
+
+/// Runs a network one button press at a time, from every module starting off
+/// (flip-flops off, conjunctions remembering a low pulse from each input).
+pub struct Simulator<'n, 'a> {
+    network: &'n Network<'a>,
+    // Each module's outputs, with the slot in `memory` the pulse is remembered in
+    wiring: Vec<Vec<(ModuleId, usize)>>,
+    flipflops: Vec<bool>,
+    // The last pulse each conjunction received from each input, one slot per wire
+    memory: Vec<bool>,
+    high_inputs: Vec<usize>,
+    queue: VecDeque<(ModuleId, ModuleId, usize, Pulse)>,
+    presses: usize,
+}
+
+impl<'n, 'a> Simulator<'n, 'a> {
+    pub fn new(network: &'n Network<'a>) -> Simulator<'n, 'a> {
+        let mut slots = 0;
+        let wiring = network
+            .ids()
+            .map(|id| {
+                network
+                    .outputs(id)
+                    .iter()
+                    .map(|&output| {
+                        slots += 1;
+                        (output, slots - 1)
+                    })
+                    .collect()
+            })
+            .collect();
+
+        Simulator {
+            network,
+            wiring,
+            flipflops: vec![false; network.len()],
+            memory: vec![false; slots],
+            high_inputs: vec![0; network.len()],
+            queue: VecDeque::with_capacity(512),
+            presses: 0,
+        }
+    }
+
+    /// Number of times the button has been pressed.
+    pub fn presses(&self) -> usize {
+        self.presses
+    }
+
+    /// Whether each flip-flop is on, indexed by module ID, and the last pulse
+    /// each conjunction received on each wire. Together these determine
+    /// everything about future presses.
+    pub fn state(&self) -> (&[bool], &[bool]) {
+        (&self.flipflops, &self.memory)
+    }
+
+    pub fn press(&mut self) -> PressStats {
+        self.press_traced(&mut NoTrace)
+    }
+
+    /// Presses the button and runs until every pulse has been delivered.
+    pub fn press_traced(&mut self, sink: &mut impl TraceSink) -> PressStats {
+        self.presses += 1;
+        let mut stats = PressStats::default();
+
+        let button = self.network.button();
+        for &(dest, slot) in self.wiring[button].iter() {
+            self.queue.push_back((button, dest, slot, Pulse::Low));
+        }
+
+        while let Some((src, dest, slot, pulse)) = self.queue.pop_front() {
+            match pulse {
+                Pulse::Low => stats.low += 1,
+                Pulse::High => stats.high += 1,
+            }
+            sink.record(&PulseEvent {
+                press: self.presses,
+                src,
+                dest,
+                pulse,
+            });
+
+            let output = match self.network.typ(dest) {
+                ModuleType::Broadcaster => pulse,
+                ModuleType::Flipflop => {
+                    if pulse == Pulse::High {
+                        continue;
+                    }
+                    stats.flips += 1;
+                    self.flipflops[dest] = !self.flipflops[dest];
+                    if self.flipflops[dest] {
+                        Pulse::High
+                    } else {
+                        Pulse::Low
+                    }
+                }
+                ModuleType::Conjunction => {
+                    let high = pulse == Pulse::High;
+                    if self.memory[slot] != high {
+                        self.memory[slot] = high;
+                        if high {
+                            self.high_inputs[dest] += 1;
+                        } else {
+                            self.high_inputs[dest] -= 1;
+                        }
+                    }
+
+                    if self.high_inputs[dest] == self.network.inputs(dest).len() {
+                        Pulse::Low
+                    } else {
+                        Pulse::High
+                    }
+                }
+                ModuleType::Button | ModuleType::Output => continue,
+            };
+
+            for &(next, next_slot) in self.wiring[dest].iter() {
+                self.queue.push_back((dest, next, next_slot, output));
+            }
+        }
+
+        stats
+    }
+}
+
+/// Writes pulses in the puzzle's format, e.g. `button -low-> broadcaster`.
+pub fn format_pulse(network: &Network, event: &PulseEvent) -> String {
+    format!(
+        "{} -{}-> {}",
+        network.name(event.src),
+        event.pulse,
+        network.name(event.dest)
+    )
+}
+
+const PRESSES: usize = 1000;
+
+fn calculate_p1(network: &Network) -> usize {
+    let mut simulator = Simulator::new(network);
+    let mut total = PressStats::default();
+
+    for _ in 0..PRESSES {
+        total += simulator.press();
+    }
+
+    total.low * total.high
+}
+
+pub const END_MODULE: &str = "rx";
+
+/// A binary counter: a chain of flip-flops, least significant bit first, and
+/// a conjunction which sends a low pulse and resets the chain to 0 every
+/// `period` presses.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Counter {
+    pub bits: Vec<ModuleId>,
+    pub checker: ModuleId,
+    pub period: usize,
+}
+
+impl Counter {
+    /// The period in binary, most significant bit first - a 1 for each bit
+    /// wired into the checker.
+    pub fn pattern(&self) -> String {
+        format!("{:0width$b}", self.period, width = self.bits.len())
+    }
+}
+
+/// The first assumption of the part 2 shortcut which a network breaks.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ShapeError(pub String);
+
+impl fmt::Display for ShapeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+fn expect_type(network: &Network, id: ModuleId, typ: ModuleType) -> Result<(), ShapeError> {
+    if network.typ(id) == typ {
+        Ok(())
+    } else {
+        Err(ShapeError(format!(
+            "{} is a {:?}, not a {:?}",
+            network.name(id),
+            network.typ(id),
+            typ
+        )))
+    }
+}
+
+// Follows the chain of flip-flops from `start`, checking each is only wired
+// to the next and to its checker, that the checker is only wired to the chain
+// and its inverter, and that it resets the chain to 0. `checkers` pairs each
+// checker with its inverter.
+fn find_counter(
+    network: &Network,
+    start: ModuleId,
+    checkers: &[(ModuleId, ModuleId)],
+) -> Result<Counter, ShapeError> {
+    let mut bits = vec![];
+    let mut checker = None;
+    let mut current = start;
+
+    loop {
+        expect_type(network, current, ModuleType::Flipflop)?;
+        if bits.contains(&current) {
+            return Err(ShapeError(format!(
+                "the chain of flip-flops from {} loops back to {}",
+                network.name(start),
+                network.name(current)
+            )));
+        }
+        bits.push(current);
+
+        let mut next = None;
+        for &output in network.outputs(current) {
+            match network.typ(output) {
+                ModuleType::Flipflop if next.is_none() => next = Some(output),
+                ModuleType::Conjunction
+                    if checkers.iter().any(|&(c, _)| c == output)
+                        && checker.is_none_or(|c| c == output) =>
+                {
+                    checker = Some(output)
+                }
+                _ => {
+                    return Err(ShapeError(format!(
+                        "{} outputs to {}, which isn't the next bit or its counter's checker",
+                        network.name(current),
+                        network.name(output)
+                    )))
+                }
+            }
+        }
+
+        match next {
+            Some(next) => current = next,
+            None => break,
+        }
+    }
+
+    // Counting needs a bit for each power of 2 up to the period
+    if bits.len() >= usize::BITS as usize {
+        return Err(ShapeError(format!(
+            "the chain of flip-flops from {} has {} bits, too many to count with",
+            network.name(start),
+            bits.len()
+        )));
+    }
+
+    let checker = checker.ok_or_else(|| {
+        ShapeError(format!(
+            "the chain of flip-flops from {} isn't wired to a checker",
+            network.name(start)
+        ))
+    })?;
+    let inverter = checkers
+        .iter()
+        .find(|&&(c, _)| c == checker)
+        .map(|&(_, inverter)| inverter)
+        .expect("checker came from checkers");
+
+    if let Some(&input) = network
+        .inputs(checker)
+        .iter()
+        .find(|input| !bits.contains(input))
+    {
+        return Err(ShapeError(format!(
+            "{} has an input from {}, which isn't a bit of its counter",
+            network.name(checker),
+            network.name(input)
+        )));
+    }
+    if let Some(&output) = network
+        .outputs(checker)
+        .iter()
+        .find(|&&output| output != inverter && !bits.contains(&output))
+    {
+        return Err(ShapeError(format!(
+            "{} outputs to {}, which isn't a bit of its counter or its inverter",
+            network.name(checker),
+            network.name(output)
+        )));
+    }
+
+    for (idx, &bit) in bits.iter().enumerate() {
+        let previous = if idx == 0 {
+            network.broadcaster()
+        } else {
+            bits[idx - 1]
+        };
+        if let Some(&input) = network
+            .inputs(bit)
+            .iter()
+            .find(|&&input| input != previous && input != checker)
+        {
+            return Err(ShapeError(format!(
+                "{} has an input from {}, which isn't the previous bit or its counter's checker",
+                network.name(bit),
+                network.name(input)
+            )));
+        }
+    }
+
+    let bit_values = |wired: &dyn Fn(ModuleId) -> bool| -> usize {
+        bits.iter()
+            .enumerate()
+            .filter(|&(_, &bit)| wired(bit))
+            .map(|(idx, _)| 1 << idx)
+            .sum()
+    };
+    let period = bit_values(&|bit| network.outputs(bit).contains(&checker));
+    let resets = bit_values(&|bit| network.outputs(checker).contains(&bit));
+
+    // Reaching the period sets exactly the bits wired into the checker, and
+    // flipping the bits it's wired back to must then carry past the top bit
+    if period + resets != 1 << bits.len() {
+        return Err(ShapeError(format!(
+            "{} doesn't reset its counter to 0: the period is {} but the bits it resets add up to {}, not {}",
+            network.name(checker),
+            period,
+            resets,
+            (1 << bits.len()) - period
+        )));
+    }
+
+    Ok(Counter {
+        bits,
+        checker,
+        period,
+    })
+}
+
+/// Checks whether the network is shaped the way the part 2 shortcut assumes:
+/// `rx` is fed by a single conjunction, which is fed by inverters, each fed
+/// by the checker of a binary counter started by the broadcaster. Returns the
+/// counters if so, with `rx` first receiving a low pulse when they all reset
+/// on the same press.
+pub fn analyse(network: &Network) -> Result<Vec<Counter>, ShapeError> {
+    let end = network
+        .id(END_MODULE)
+        .ok_or_else(|| ShapeError(format!("there's no {} module", END_MODULE)))?;
+
+    let &[final_module] = network.inputs(end) else {
+        return Err(ShapeError(format!(
+            "{} has {} inputs, not 1",
+            END_MODULE,
+            network.inputs(end).len()
+        )));
+    };
+    expect_type(network, final_module, ModuleType::Conjunction)?;
+
+    let mut checkers = vec![];
+    for &inverter in network.inputs(final_module) {
+        expect_type(network, inverter, ModuleType::Conjunction)?;
+        let &[checker] = network.inputs(inverter) else {
+            return Err(ShapeError(format!(
+                "{} has {} inputs, so isn't an inverter",
+                network.name(inverter),
+                network.inputs(inverter).len()
+            )));
+        };
+        expect_type(network, checker, ModuleType::Conjunction)?;
+        checkers.push((checker, inverter));
+    }
+
+    let starts = network.outputs(network.broadcaster());
+    if starts.len() != checkers.len() {
+        return Err(ShapeError(format!(
+            "the broadcaster starts {} chains, but {} has {} inputs",
+            starts.len(),
+            network.name(final_module),
+            checkers.len()
+        )));
+    }
+
+    let counters = starts
+        .iter()
+        .map(|&start| find_counter(network, start, &checkers))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    for (idx, counter) in counters.iter().enumerate() {
+        if counters[..idx].iter().any(|c| c.checker == counter.checker) {
+            return Err(ShapeError(format!(
+                "{} checks more than one counter",
+                network.name(counter.checker)
+            )));
+        }
+    }
+
+    Ok(counters)
+}
+
+/// When a module first receives a low pulse, found by simulation.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum FirstLow {
+    /// On this press, counting from 1.
+    Press(usize),
+    /// Never: the network is back in an earlier state after this many
+    /// presses, without the module having received one.
+    Never(usize),
+    /// Still unknown after this many presses.
+    GaveUp(usize),
+}
+
+fn state_key(simulator: &Simulator) -> BitVec {
+    let (flipflops, memory) = simulator.state();
+    flipflops.iter().chain(memory).copied().collect()
+}
+
+/// Presses the button until `target` receives a low pulse, the network
+/// repeats a state, or `max_presses` is reached.
+pub fn first_low_pulse(network: &Network, target: ModuleId, max_presses: usize) -> FirstLow {
+    let mut simulator = Simulator::new(network);
+
+    // Brent's algorithm: each state is compared with one saved state, which
+    // moves on after 1, 2, 4, ... presses, so a loop of any length is found
+    // soon after it starts while only ever storing one state
+    let mut saved = state_key(&simulator);
+    let mut power = 1;
+    let mut since_saved = 0;
+
+    loop {
+        if simulator.presses() >= max_presses {
+            return FirstLow::GaveUp(simulator.presses());
+        }
+
+        let mut received = false;
+        simulator.press_traced(&mut |event: &PulseEvent| {
+            received |= event.dest == target && event.pulse == Pulse::Low;
+        });
+        if received {
+            return FirstLow::Press(simulator.presses());
+        }
+
+        let state = state_key(&simulator);
+        if state == saved {
+            return FirstLow::Never(simulator.presses());
+        }
+
+        since_saved += 1;
+        if since_saved == power {
+            saved = state;
+            power *= 2;
+            since_saved = 0;
+        }
+    }
+}
+
+/// The first press on which every counter resets at once.
+pub fn combined_period(counters: &[Counter]) -> usize {
+    counters.iter().fold(1, |acc, c| acc.lcm(&c.period))
+}
+
+/// The network as a Graphviz graph, with the modules of `counters` and the
+/// wiring along each chain of bits highlighted.
+pub fn to_dot(network: &Network, counters: &[Counter]) -> DotGraph {
+    let mut graph = DotGraph::directed();
+    graph.shape("button", "doublecircle");
+    graph.shape("broadcaster", "circle");
+    graph.shape("flip-flop", "box");
+    graph.shape("conjunction", "diamond");
+    graph.shape("output", "plaintext");
+
+    for id in network.ids() {
+        let kind = match network.typ(id) {
+            ModuleType::Button => "button",
+            ModuleType::Broadcaster => "broadcaster",
+            ModuleType::Flipflop => "flip-flop",
+            ModuleType::Conjunction => "conjunction",
+            ModuleType::Output => "output",
+        };
+        graph.node(network.name(id), Some(kind));
+
+        for &output in network.outputs(id) {
+            graph.edge(network.name(id), network.name(output), None);
+        }
+    }
+
+    for counter in counters {
+        graph.highlight_node(network.name(counter.checker));
+        for &bit in &counter.bits {
+            graph.highlight_node(network.name(bit));
+        }
+        for pair in counter.bits.windows(2) {
+            graph.highlight_edge(network.name(pair[0]), network.name(pair[1]));
+        }
+    }
+
+    graph
+}
+
+// Networks which aren't binary counters are only simulated this far
+const MAX_SIMULATED_PRESSES: usize = 1_000_000;
+
+// Aggressively optimized for the AoC inputs:
+// - The structure of the inputs is 4 12-bit cycling binary counters
+// - All of them must "line up" for the final output to fire (which
+//   is the final puzzle answer we want)
+//
+// This method extracts those binary counters directly, never needing
+// to "push the button". Networks of any other shape are simulated instead,
+// which is intractable for anything much bigger than the examples.
+fn calculate_p2(network: &Network) -> Result<usize, String> {
+    let shape_error = match analyse(network) {
+        Ok(counters) => return Ok(combined_period(&counters)),
+        Err(e) => e,
+    };
+
+    let end = network
+        .id(END_MODULE)
+        .ok_or_else(|| format!("there's no {} module", END_MODULE))?;
+    match first_low_pulse(network, end, MAX_SIMULATED_PRESSES) {
+        FirstLow::Press(presses) => Ok(presses),
+        FirstLow::Never(presses) => Err(format!(
+            "{} never gets a low pulse: the network repeats a state after {} presses",
+            END_MODULE, presses
+        )),
+        FirstLow::GaveUp(presses) => Err(format!(
+            "{} gets no low pulse in {} presses, and the network can't be analysed: {}",
+            END_MODULE, presses, shape_error
+        )),
+    }
+}
+
+pub struct Day20;
+
+impl Solution for Day20 {
+    type Input<'a> = Network<'a>;
+    type Answer1 = usize;
+    // The number of presses, or why there isn't one
+    type Answer2 = Result<usize, String>;
+
+    fn parse<'a>(&self, raw_inp: &'a str) -> Result<Self::Input<'a>, ParseError> {
+        parse(raw_inp)
+    }
+
+    fn part1(&self, input: &Self::Input<'_>) -> usize {
+        calculate_p1(input)
+    }
+
+    fn part2(&self, input: &Self::Input<'_>) -> Option<Result<usize, String>> {
+        Some(calculate_p2(input))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Day;
+
+    aoc_tests! {
+        real_input: "2023_20";
+
+        p1 (part 1): |inp| calculate_p1(&parse(inp).unwrap()) => {
+            example("2023_20") => 32000000,
+            example_2("2023_20_2") => 11687500,
+        }
+
+        p2 (part 2): |inp| calculate_p2(&parse(inp).unwrap()).unwrap() => {}
+    }
+
+    #[test]
+    fn test_trace() {
+        let network = parse(fixture!("2023_20_2")).unwrap();
+        assert_eq!(
+            network.typ(network.id("output").unwrap()),
+            ModuleType::Output
+        );
+
+        let mut simulator = Simulator::new(&network);
+        let mut trace = vec![];
+        let stats = simulator
+            .press_traced(&mut |event: &PulseEvent| trace.push(format_pulse(&network, event)));
+
+        assert_eq!(
+            trace,
+            [
+                "button -low-> broadcaster",
+                "broadcaster -low-> a",
+                "a -high-> inv",
+                "a -high-> con",
+                "inv -low-> b",
+                "con -high-> output",
+                "b -high-> con",
+                "con -low-> output",
+            ]
+        );
+        assert_eq!(
+            stats,
+            PressStats {
+                low: 4,
+                high: 4,
+                flips: 2
+            }
+        );
+
+        // The network's back where it started after 4 presses
+        let start = simulator.state().0.to_vec();
+        let stats = (0..3).map(|_| simulator.press()).collect::<Vec<_>>();
+        assert_eq!((stats[0].low, stats[0].high), (4, 2));
+        assert_eq!(simulator.presses(), 4);
+        assert_ne!(simulator.state().0, start);
+        simulator.press();
+        assert_eq!(simulator.state().0, start);
+    }
+
+    // Two counters, with periods 0b101 and 0b111
+    const COUNTERS: &str = "\
+broadcaster -> a0, b0
+%a0 -> a1, ca
+%a1 -> a2
+%a2 -> ca
+&ca -> a0, a1, ia
+&ia -> fin
+%b0 -> b1, cb
+%b1 -> b2, cb
+%b2 -> cb
+&cb -> b0, ib
+&ib -> fin
+&fin -> rx
+";
+
+    #[test]
+    fn test_analyse() {
+        let network = parse(COUNTERS).unwrap();
+        let counters = analyse(&network).unwrap();
+
+        let names = |ids: &[ModuleId]| ids.iter().map(|&id| network.name(id)).collect::<Vec<_>>();
+        assert_eq!(names(&counters[0].bits), ["a0", "a1", "a2"]);
+        assert_eq!(network.name(counters[0].checker), "ca");
+        assert_eq!(counters[0].pattern(), "101");
+        assert_eq!(
+            counters.iter().map(|c| c.period).collect::<Vec<_>>(),
+            [5, 7]
+        );
+
+        // The shortcut agrees with simulating
+        let rx = network.id("rx").unwrap();
+        assert_eq!(first_low_pulse(&network, rx, 100), FirstLow::Press(35));
+        assert_eq!(calculate_p2(&network), Ok(35));
+    }
+
+    #[test]
+    fn test_to_dot() {
+        let network = parse(COUNTERS).unwrap();
+        let dot = to_dot(&network, &analyse(&network).unwrap()).to_string();
+
+        assert!(dot.starts_with("digraph {\n    \"button\" [shape=doublecircle"));
+        assert!(dot.contains("\"a1\" [shape=box, tooltip=\"flip-flop\", color=red, penwidth=3];"));
+        assert!(dot.contains("\"fin\" [shape=diamond, tooltip=\"conjunction\"];"));
+        assert!(dot.contains("\"a1\" -> \"a2\" [color=red, penwidth=3];"));
+        assert!(dot.contains("\"a2\" -> \"ca\";"));
+    }
+
+    #[test]
+    fn test_shape_errors() {
+        let error = |raw_inp: &str| analyse(&parse(raw_inp).unwrap()).unwrap_err().0;
+
+        let example = fixture!("2023_20");
+        assert_eq!(error(example), "there's no rx module");
+
+        // Resetting a2 as well means the counter never wraps to 0
+        let bad_reset = COUNTERS.replace("&ca -> a0, a1, ia", "&ca -> a0, a1, a2, ia");
+        assert!(error(&bad_reset).starts_with("ca doesn't reset its counter to 0"));
+
+        let extra_wire = COUNTERS.replace("%a1 -> a2", "%a1 -> a2, b1");
+        assert!(error(&extra_wire).starts_with("a1 outputs to b1"));
+
+        let checker_input = format!("{}%x -> ca\n", COUNTERS);
+        assert!(error(&checker_input).starts_with("ca has an input from x"));
+
+        let checker_output = COUNTERS.replace("&ca -> a0, a1, ia", "&ca -> a0, a1, ia, b2");
+        assert!(error(&checker_output).starts_with("ca outputs to b2"));
+
+        // 64 bits can't be counted in a usize
+        let mut long_chain = "broadcaster -> f0\n".to_string();
+        for bit in 0..64 {
+            long_chain += &format!("%f{} -> f{}, c\n", bit, bit + 1);
+        }
+        long_chain += "%f64 -> c\n&c -> f0, i\n&i -> fin\n&fin -> rx\n";
+        assert!(error(&long_chain).contains("has 65 bits"));
+
+        let not_inverter = COUNTERS.replace("&cb -> b0, ib", "&cb -> b0, ib, ia");
+        assert!(error(&not_inverter).starts_with("ia has 2 inputs"));
+
+        // With no shortcut, simulating finds whether rx ever gets a low pulse
+        let flipflop = parse("broadcaster -> a\n%a -> rx\n").unwrap();
+        assert!(analyse(&flipflop).is_err());
+        assert_eq!(
+            first_low_pulse(&flipflop, flipflop.id("rx").unwrap(), 100),
+            FirstLow::Press(2)
+        );
+        assert_eq!(
+            first_low_pulse(&flipflop, flipflop.id("rx").unwrap(), 1),
+            FirstLow::GaveUp(1)
+        );
+        let inverter = parse("broadcaster -> inv\n&inv -> rx\n").unwrap();
+        assert_eq!(
+            first_low_pulse(&inverter, inverter.id("rx").unwrap(), 100),
+            FirstLow::Never(1)
+        );
+        assert!(calculate_p2(&inverter)
+            .unwrap_err()
+            .starts_with("rx never gets"));
+        // Which fails the run, rather than being given as the answer
+        let Err(err) = Day20.run("broadcaster -> inv\n&inv -> rx\n") else {
+            panic!("part 2 answered without a low pulse to rx");
+        };
+        assert!(format!("{:#}", err).starts_with("can't solve part 2: rx never gets"));
+        assert_eq!(
+            calculate_p2(&parse(fixture!("2023_20")).unwrap()),
+            Err("there's no rx module".to_string())
+        );
+    }
+}
@@ -1,12 +1,17 @@
-#![cfg_attr(feature = "bench", feature(test))]
-use advent_of_code_2023::grid_util::make_byte_grid;
-use advent_of_code_2023::{Cli, Parser};
+use crate::grid_util::make_tile_grid;
+use crate::parse_util::ParseError;
+use crate::Solution;
 use ahash::AHashSet;
 use ndarray::Array2;
-use std::fs;
 
-fn parse(raw_inp: &str) -> Array2<u8> {
-    make_byte_grid(raw_inp.trim())
+fn parse(raw_inp: &str) -> Result<Array2<u8>, ParseError> {
+    let grid = make_tile_grid(raw_inp, ".#S")?;
+
+    if !grid.iter().any(|&b| b == b'S') {
+        return Err(ParseError::new(raw_inp.trim(), "a start tile 'S'"));
+    }
+
+    Ok(grid)
 }
 
 fn calculate_p1<const N: usize>(data: &Array2<u8>) -> usize {
@@ -261,54 +266,39 @@ fn calculate_p2<const N: u64>(data: &Array2<u8>) -> u64 {
         + parameters.even_tile_score
 }
 
-fn main() {
-    let args = Cli::parse();
+pub struct Day21;
+
+impl Solution for Day21 {
+    type Input<'a> = Array2<u8>;
+    type Answer1 = usize;
+    type Answer2 = u64;
 
-    let inp = fs::read_to_string(args.input).expect("can't open input file");
+    fn parse<'a>(&self, raw_inp: &'a str) -> Result<Self::Input<'a>, ParseError> {
+        parse(raw_inp)
+    }
+
+    fn part1(&self, input: &Self::Input<'_>) -> usize {
+        calculate_p1::<64>(input)
+    }
 
-    let data = parse(&inp);
-    let p1 = calculate_p1::<64>(&data);
-    let p2 = calculate_p2::<26501365>(&data);
-    println!("{}\n{}", p1, p2);
+    fn part2(&self, input: &Self::Input<'_>) -> Option<u64> {
+        Some(calculate_p2::<26501365>(input))
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    const EXAMPLE_DATA: &str = include_str!("../../inputs/examples/2023_21");
-    const REAL_DATA: &str = include_str!("../../inputs/real/2023_21");
-
-    #[test]
-    fn test_p1_example() {
-        assert_eq!(calculate_p1::<6>(&parse(EXAMPLE_DATA)), 16);
-    }
+    aoc_tests! {
+        real_input: "2023_21";
 
-    #[test]
-    fn test_p1_real() {
-        assert_eq!(calculate_p1::<64>(&parse(REAL_DATA)), 3649);
-    }
+        p1_6_steps: |inp| calculate_p1::<6>(&parse(inp).unwrap()) => {
+            example("2023_21") => 16,
+        }
 
-    #[test]
-    fn test_p2_real() {
-        assert_eq!(calculate_p2::<26501365>(&parse(REAL_DATA)), 612941134797232);
-    }
+        p1 (part 1): |inp| calculate_p1::<64>(&parse(inp).unwrap()) => {}
 
-    #[cfg(feature = "bench")]
-    mod benches {
-        extern crate test;
-        use test::{black_box, Bencher};
-
-        use super::*;
-
-        #[bench]
-        fn bench(b: &mut Bencher) {
-            b.iter(|| {
-                let data = parse(black_box(REAL_DATA));
-                let p1 = calculate_p1::<64>(&data);
-                let p2 = calculate_p2::<26501365>(&data);
-                (p1, p2)
-            });
-        }
+        p2 (part 2): |inp| calculate_p2::<26501365>(&parse(inp).unwrap()) => {}
     }
 }
@@ -1,7 +1,6 @@
-#![cfg_attr(feature = "bench", feature(test))]
-use advent_of_code_2023::{Cli, Parser};
-use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
-use std::fs;
+use crate::parse_util::{parse_number, split_once, ParseError};
+use crate::Solution;
+use rayon::prelude::*;
 
 #[derive(Eq, PartialEq, Debug, Clone, Copy, Hash)]
 struct Brick {
@@ -95,29 +94,32 @@ impl Brick {
     }
 }
 
-fn parse(raw_inp: &str) -> Vec<Brick> {
-    raw_inp
-        .trim()
-        .lines()
-        .map(|line| {
-            let (b1, b2) = line.split_once('~').unwrap();
-
-            let (x1, bb1) = b1.split_once(',').unwrap();
-            let (y1, z1) = bb1.split_once(',').unwrap();
-
-            let (x2, bb2) = b2.split_once(',').unwrap();
-            let (y2, z2) = bb2.split_once(',').unwrap();
-
-            Brick {
-                x1: x1.parse().unwrap(),
-                y1: y1.parse().unwrap(),
-                z1: z1.parse().unwrap(),
-                x2: x2.parse().unwrap(),
-                y2: y2.parse().unwrap(),
-                z2: z2.parse().unwrap(),
-            }
-        })
-        .collect()
+pub struct Data {
+    pile: Vec<Brick>,
+    biggest_brick_z: i64,
+}
+
+fn parse_brick(line: &str) -> Result<Brick, ParseError> {
+    let (b1, b2) = split_once(line, "~")?;
+
+    let (x1, bb1) = split_once(b1, ",")?;
+    let (y1, z1) = split_once(bb1, ",")?;
+
+    let (x2, bb2) = split_once(b2, ",")?;
+    let (y2, z2) = split_once(bb2, ",")?;
+
+    Ok(Brick {
+        x1: parse_number(x1)?,
+        y1: parse_number(y1)?,
+        z1: parse_number(z1)?,
+        x2: parse_number(x2)?,
+        y2: parse_number(y2)?,
+        z2: parse_number(z2)?,
+    })
+}
+
+fn parse_bricks(raw_inp: &str) -> Result<Vec<Brick>, ParseError> {
+    raw_inp.trim().lines().map(parse_brick).collect()
 }
 
 fn settled_bricks(data: &[Brick]) -> Vec<Brick> {
@@ -178,90 +180,89 @@ fn count_falling_bricks(data: &[&Brick], min_z: i64, max_z: i64) -> i64 {
         .unwrap_or(0)
 }
 
-fn calculate(data: &[Brick]) -> (i64, i64) {
-    let pile = settled_bricks(data);
+// The snapshot is only useful once the bricks have settled, so settle them
+// up-front rather than separately in each part.
+fn parse(raw_inp: &str) -> Result<Data, ParseError> {
+    let bricks = parse_bricks(raw_inp)?;
 
-    let biggest_brick_z = data
+    let biggest_brick_z = bricks
         .iter()
         .map(|b| b.max_z() - b.min_z() + 1)
         .max()
         .unwrap_or(1);
 
-    pile.par_iter()
-        .map(|removed_brick| {
-            let unsupported = pile
-                .iter()
-                .filter(|b| b.is_supported_by(removed_brick))
-                .find(|b| !b.is_supported(&pile, removed_brick));
-
-            if let Some(_zapped) = unsupported {
-                let zapped = pile
-                    .iter()
-                    .filter(|&b| b != removed_brick)
-                    .filter(|&b| b.min_z() > removed_brick.min_z() - biggest_brick_z)
-                    .collect::<Vec<_>>();
-
-                let min_z = 1.max(removed_brick.min_z());
-                let max_z = min_z + biggest_brick_z;
-                let fallers = count_falling_bricks(&zapped, min_z, max_z);
-                (0, fallers)
-            } else {
-                (1, 0)
-            }
-        })
-        .reduce(|| (0, 0), |a, b| (a.0 + b.0, a.1 + b.1))
+    Ok(Data {
+        pile: settled_bricks(&bricks),
+        biggest_brick_z,
+    })
+}
+
+fn can_disintegrate(pile: &[Brick], removed_brick: &Brick) -> bool {
+    pile.iter()
+        .filter(|b| b.is_supported_by(removed_brick))
+        .all(|b| b.is_supported(pile, removed_brick))
+}
+
+fn calculate_p1(data: &Data) -> usize {
+    data.pile
+        .par_iter()
+        .filter(|removed_brick| can_disintegrate(&data.pile, removed_brick))
+        .count()
 }
 
-fn main() {
-    let args = Cli::parse();
+fn calculate_p2(data: &Data) -> i64 {
+    let pile = &data.pile;
+    let biggest_brick_z = data.biggest_brick_z;
 
-    let inp = fs::read_to_string(args.input).expect("can't open input file");
+    pile.par_iter()
+        .filter(|removed_brick| !can_disintegrate(pile, removed_brick))
+        .map(|removed_brick| {
+            let zapped = pile
+                .iter()
+                .filter(|&b| b != removed_brick)
+                .filter(|&b| b.min_z() > removed_brick.min_z() - biggest_brick_z)
+                .collect::<Vec<_>>();
 
-    let data = parse(&inp);
-    let (p1, p2) = calculate(&data);
-    println!("{}\n{}", p1, p2);
+            let min_z = 1.max(removed_brick.min_z());
+            let max_z = min_z + biggest_brick_z;
+            count_falling_bricks(&zapped, min_z, max_z)
+        })
+        .sum()
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+pub struct Day22;
 
-    const EXAMPLE_DATA: &str = include_str!("../../inputs/examples/2023_22");
-    const REAL_DATA: &str = include_str!("../../inputs/real/2023_22");
+impl Solution for Day22 {
+    type Input<'a> = Data;
+    type Answer1 = usize;
+    type Answer2 = i64;
 
-    #[test]
-    fn test_p1_example() {
-        assert_eq!(calculate(&parse(EXAMPLE_DATA)).0, 5);
+    fn parse<'a>(&self, raw_inp: &'a str) -> Result<Self::Input<'a>, ParseError> {
+        parse(raw_inp)
     }
 
-    #[test]
-    fn test_p2_example() {
-        assert_eq!(calculate(&parse(EXAMPLE_DATA)).1, 7);
+    fn part1(&self, input: &Self::Input<'_>) -> usize {
+        calculate_p1(input)
     }
 
-    #[test]
-    fn test_p1_real() {
-        assert_eq!(calculate(&parse(REAL_DATA)).0, 507);
+    fn part2(&self, input: &Self::Input<'_>) -> Option<i64> {
+        Some(calculate_p2(input))
     }
+}
 
-    #[test]
-    fn test_p2_real() {
-        assert_eq!(calculate(&parse(REAL_DATA)).1, 51733);
-    }
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    #[cfg(feature = "bench")]
-    mod benches {
-        extern crate test;
-        use test::{black_box, Bencher};
+    aoc_tests! {
+        real_input: "2023_22";
 
-        use super::*;
+        p1 (part 1): |inp| calculate_p1(&parse(inp).unwrap()) => {
+            example("2023_22") => 5,
+        }
 
-        #[bench]
-        fn bench(b: &mut Bencher) {
-            b.iter(|| {
-                let data = parse(black_box(REAL_DATA));
-                calculate(&data)
-            });
+        p2 (part 2): |inp| calculate_p2(&parse(inp).unwrap()) => {
+            example("2023_22") => 7,
         }
     }
 }
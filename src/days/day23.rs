@@ -0,0 +1,366 @@
+use crate::graphviz::DotGraph;
+use crate::grid_util::{render, Dir, Grid, Overlay, Pos, RenderFormat, Rgb};
+use crate::parse_util::ParseError;
+use crate::Solution;
+use ahash::{AHashMap, AHashSet};
+use rayon::prelude::*;
+
+pub fn parse(raw_inp: &str) -> Result<Grid<u8>, ParseError> {
+    let grid = Grid::parse_tiles(raw_inp, "#.^>v<")?;
+
+    let lines = raw_inp.trim().lines().collect::<Vec<_>>();
+    if grid.rows() < 2 {
+        return Err(ParseError::new(lines[0], "at least 2 rows"));
+    }
+    if start(&grid).is_none() {
+        return Err(ParseError::new(lines[0], "a start tile '.' in the top row"));
+    }
+    if end(&grid).is_none() {
+        return Err(ParseError::new(
+            lines[lines.len() - 1],
+            "an end tile '.' in the bottom row",
+        ));
+    }
+
+    Ok(grid)
+}
+
+type CostMap = AHashMap<Pos, AHashMap<Pos, usize>>;
+
+fn longest_path(
+    cost_map: &CostMap,
+    visited: &mut AHashSet<Pos>,
+    pos: Pos,
+    target: Pos,
+    cost_so_far: usize,
+) -> Option<usize> {
+    if pos == target {
+        return Some(cost_so_far);
+    }
+
+    visited.insert(pos);
+
+    let res = cost_map
+        .get(&pos)
+        .expect("landed at an invalid position?")
+        .par_iter()
+        .filter_map(|(new_pos, cost)| {
+            if visited.contains(new_pos) {
+                None
+            } else {
+                longest_path(
+                    cost_map,
+                    &mut visited.clone(),
+                    *new_pos,
+                    target,
+                    cost_so_far + cost,
+                )
+            }
+        })
+        .max();
+
+    visited.remove(&pos);
+
+    res
+}
+
+fn is_path(data: &Grid<u8>, pos: Pos) -> bool {
+    data[pos] != b'#'
+}
+
+fn slope(dir: Dir) -> u8 {
+    match dir {
+        Dir::Up => b'^',
+        Dir::Right => b'>',
+        Dir::Down => b'v',
+        Dir::Left => b'<',
+    }
+}
+
+fn get_decision_points(data: &Grid<u8>) -> AHashSet<Pos> {
+    data.iter()
+        .filter(|&(pos, _)| is_path(data, pos))
+        .filter(|&(pos, _)| data.neighbours4(pos).filter(|&n| is_path(data, n)).count() > 2)
+        .map(|(pos, _)| pos)
+        .collect()
+}
+
+// Follows the trail from `start` through `first_step` to the first of `targets`,
+// or returns `None` if it's a dead end
+fn pathfind_to_any(
+    data: &Grid<u8>,
+    start: Pos,
+    first_step: Option<Pos>,
+    targets: &AHashSet<Pos>,
+) -> Option<(Pos, usize)> {
+    let mut cost = 1;
+
+    let mut last_pos = start;
+    let mut pos = first_step.filter(|&pos| is_path(data, pos))?;
+
+    while !targets.contains(&pos) {
+        // Without junctions, a trail can only come back round to where it
+        // started, which isn't a target
+        if cost > data.rows() * data.columns() {
+            return None;
+        }
+
+        let new_pos = data
+            .neighbours4(pos)
+            .filter(|&new_pos| new_pos != last_pos)
+            .find(|&new_pos| is_path(data, new_pos))?;
+
+        last_pos = pos;
+        pos = new_pos;
+        cost += 1;
+    }
+
+    Some((pos, cost))
+}
+
+fn make_cost_map<const PART: u8>(data: &Grid<u8>, decision_points: &AHashSet<Pos>) -> CostMap {
+    decision_points
+        .iter()
+        .map(|&pos| {
+            let cost_map = Dir::ALL
+                .into_iter()
+                .filter_map(|dir| Some((dir, data.step(pos, dir)?)))
+                .filter(|&(dir, new_pos)| {
+                    let next_tile = data[new_pos];
+                    PART == 2 || next_tile == slope(dir) || next_tile == b'.'
+                })
+                // Dead ends lead nowhere, so are left out
+                .filter_map(|(_, new_pos)| {
+                    pathfind_to_any(data, pos, Some(new_pos), decision_points)
+                })
+                .collect();
+
+            (pos, cost_map)
+        })
+        .collect()
+}
+
+fn start(data: &Grid<u8>) -> Option<Pos> {
+    data.iter()
+        .find(|&(pos, &t)| pos.y == 0 && t == b'.')
+        .map(|(pos, _)| pos)
+}
+
+fn end(data: &Grid<u8>) -> Option<Pos> {
+    data.iter()
+        .find(|&(pos, &t)| pos.y == data.rows() - 1 && t == b'.')
+        .map(|(pos, _)| pos)
+}
+
+// Both are checked for by `parse`
+fn find_start(data: &Grid<u8>) -> Pos {
+    start(data).expect("can't find start")
+}
+
+fn find_end(data: &Grid<u8>) -> Pos {
+    end(data).expect("can't find end")
+}
+
+fn calculate<const PART: u8>(data: &Grid<u8>) -> Result<usize, String> {
+    let start = find_start(data);
+    let end = find_end(data);
+
+    let mut decision_points = get_decision_points(data);
+    decision_points.insert(start);
+    decision_points.insert(end);
+
+    let cost_map = make_cost_map::<PART>(data, &decision_points);
+    let mut visited = AHashSet::default();
+
+    // There is only one route, which is constant, from start to a decision point, and
+    // also from end to a decision point. This lets us prune search space somewhat, by
+    // just adding these offsets and then eliminating start/end from the graph.
+    let no_route = || "there's no route from the start to the end".to_string();
+    let real_start = pathfind_to_any(data, start, data.step(start, Dir::Down), &decision_points)
+        .ok_or_else(no_route)?;
+    // A single trail with no junctions along it
+    if real_start.0 == end {
+        return Ok(real_start.1);
+    }
+    let real_end = pathfind_to_any(data, end, data.step(end, Dir::Up), &decision_points)
+        .ok_or_else(no_route)?;
+
+    longest_path(
+        &cost_map,
+        &mut visited,
+        real_start.0,
+        real_end.0,
+        real_start.1 + real_end.1,
+    )
+    .ok_or_else(no_route)
+}
+
+/// The trails between junctions as a Graphviz graph, labelled with their
+/// lengths, and optionally the start and end highlighted. Trails are one-way
+/// over slopes in part 1, and two-way in part 2.
+pub fn to_dot(data: &Grid<u8>, part: u8, highlight: bool) -> DotGraph {
+    let start = find_start(data);
+    let end = find_end(data);
+
+    let mut decision_points = get_decision_points(data);
+    decision_points.insert(start);
+    decision_points.insert(end);
+
+    let (cost_map, mut graph) = if part == 1 {
+        (
+            make_cost_map::<1>(data, &decision_points),
+            DotGraph::directed(),
+        )
+    } else {
+        (
+            make_cost_map::<2>(data, &decision_points),
+            DotGraph::undirected(),
+        )
+    };
+
+    let name = |pos: Pos| format!("{},{}", pos.y, pos.x);
+
+    // Sorted so the output doesn't depend on hash order
+    let mut nodes = decision_points.into_iter().collect::<Vec<_>>();
+    nodes.sort();
+
+    for &pos in &nodes {
+        let kind = if pos == start {
+            "start"
+        } else if pos == end {
+            "end"
+        } else {
+            "junction"
+        };
+        graph.node(name(pos), Some(kind));
+
+        // Routes stop at the end, so trails leading back from it are never taken
+        if pos == end {
+            continue;
+        }
+
+        let mut trails = cost_map[&pos].iter().collect::<Vec<_>>();
+        trails.sort();
+        for (&next, &cost) in trails {
+            // Each two-way trail is in the cost map once from each end
+            if part == 1 || pos < next {
+                graph.edge(name(pos), name(next), Some(cost));
+            }
+        }
+    }
+
+    graph.shape("start", "doublecircle");
+    graph.shape("end", "doublecircle");
+    if highlight {
+        graph.highlight_node(name(start));
+        graph.highlight_node(name(end));
+    }
+
+    graph
+}
+
+fn colour(cell: &u8) -> Rgb {
+    match cell {
+        b'#' => [30, 90, 30],
+        b'.' => [200, 200, 200],
+        _ => [120, 120, 220],
+    }
+}
+
+pub struct Day23;
+
+impl Solution for Day23 {
+    type Input<'a> = Grid<u8>;
+    // The longest hike, or why there isn't one
+    type Answer1 = Result<usize, String>;
+    type Answer2 = Result<usize, String>;
+
+    fn parse<'a>(&self, raw_inp: &'a str) -> Result<Self::Input<'a>, ParseError> {
+        parse(raw_inp)
+    }
+
+    fn part1(&self, input: &Self::Input<'_>) -> Result<usize, String> {
+        calculate::<1>(input)
+    }
+
+    fn part2(&self, input: &Self::Input<'_>) -> Option<Result<usize, String>> {
+        Some(calculate::<2>(input))
+    }
+
+    // The trails, with the junctions the search runs between marked
+    fn render(&self, input: &Self::Input<'_>, format: RenderFormat) -> Option<Vec<u8>> {
+        let junctions = get_decision_points(input);
+
+        let overlay = Overlay {
+            positions: &junctions,
+            symbol: 'X',
+            colour: [255, 0, 0],
+        };
+
+        Some(render(
+            input,
+            format,
+            |&c| c as char,
+            colour,
+            Some(&overlay),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    aoc_tests! {
+        real_input: "2023_23";
+
+        p1 (part 1): |inp| calculate::<1>(&parse(inp).unwrap()).unwrap() => {
+            example("2023_23") => 94,
+        }
+
+        p2 (part 2): |inp| calculate::<2>(&parse(inp).unwrap()).unwrap() => {
+            example("2023_23") => 154,
+        }
+    }
+
+    #[test]
+    fn test_to_dot() {
+        let example = fixture!("2023_23");
+        let data = parse(example).unwrap();
+
+        // The start, end and 7 junctions, with slopes making every trail one-way
+        let one_way = to_dot(&data, 1, true).to_string();
+        assert!(
+            one_way.starts_with("digraph {\n    \"0,1\" [shape=doublecircle, tooltip=\"start\"")
+        );
+        assert_eq!(one_way.matches("tooltip").count(), 9);
+        assert_eq!(one_way.matches(" -> ").count(), 12);
+
+        let two_way = to_dot(&data, 2, false).to_string();
+        assert_eq!(two_way.matches(" -- ").count(), 12);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(parse("#.#\n#x#\n#.#\n").is_err());
+        assert!(parse("###\n#.#\n#.#\n").is_err());
+        assert!(parse("#.#\n#.#\n###\n").is_err());
+        assert!(parse("#.#\n").is_err());
+        assert!(parse("#.#\n#.#\n#.#\n").is_ok());
+    }
+
+    #[test]
+    fn test_no_route() {
+        // A straight trail, with no junctions
+        let data = parse("#.#\n#.#\n#.#\n").unwrap();
+        assert_eq!(calculate::<1>(&data), Ok(2));
+        assert_eq!(calculate::<2>(&data), Ok(2));
+
+        let no_route = Err("there's no route from the start to the end".to_string());
+        // Dead ends, with or without junctions
+        let data = parse("#.#\n###\n#.#\n").unwrap();
+        assert_eq!(calculate::<1>(&data), no_route);
+        let data = parse("#.###\n#...#\n#.#.#\n###.#\n#.###\n").unwrap();
+        assert_eq!(calculate::<2>(&data), no_route);
+    }
+}
@@ -1,12 +1,11 @@
-#![cfg_attr(feature = "bench", feature(test))]
-use advent_of_code_2023::{Cli, Parser};
+use crate::parse_util::{parse_number, split_once, ParseError};
+use crate::Solution;
 use itertools::Itertools;
 use ndarray::ArrayView2;
 use ndarray_linalg::Inverse;
-use std::fs;
 
 #[derive(Debug)]
-struct Hailstone {
+pub struct Hailstone {
     pos_x: i128,
     pos_y: i128,
     pos_z: i128,
@@ -44,29 +43,31 @@ impl Hailstone {
     }
 }
 
-fn parse(raw_inp: &str) -> Vec<Hailstone> {
+fn parse_hailstone(line: &str) -> Result<Hailstone, ParseError> {
+    let (pos, vel) = split_once(line.trim(), " @ ")?;
+
+    let (px, pos) = split_once(pos, ", ")?;
+    let (py, pz) = split_once(pos, ", ")?;
+
+    let (vx, vel) = split_once(vel, ", ")?;
+    let (vy, vz) = split_once(vel, ", ")?;
+
+    Ok(Hailstone {
+        pos_x: parse_number(px.trim())?,
+        pos_y: parse_number(py.trim())?,
+        pos_z: parse_number(pz.trim())?,
+        vel_x: parse_number(vx.trim())?,
+        vel_y: parse_number(vy.trim())?,
+        vel_z: parse_number(vz.trim())?,
+    })
+}
+
+fn parse(raw_inp: &str) -> Result<Vec<Hailstone>, ParseError> {
     raw_inp
         .trim()
         .lines()
         .filter(|line| !line.is_empty())
-        .map(|line| {
-            let (pos, vel) = line.trim().split_once(" @ ").unwrap();
-
-            let (px, pos) = pos.split_once(", ").unwrap();
-            let (py, pz) = pos.split_once(", ").unwrap();
-
-            let (vx, vel) = vel.split_once(", ").unwrap();
-            let (vy, vz) = vel.split_once(", ").unwrap();
-
-            Hailstone {
-                pos_x: px.trim().parse().unwrap(),
-                pos_y: py.trim().parse().unwrap(),
-                pos_z: pz.trim().parse().unwrap(),
-                vel_x: vx.trim().parse().unwrap(),
-                vel_y: vy.trim().parse().unwrap(),
-                vel_z: vz.trim().parse().unwrap(),
-            }
-        })
+        .map(parse_hailstone)
         .collect()
 }
 
@@ -132,62 +133,41 @@ fn calculate_p2(data: &[Hailstone]) -> i128 {
 const P1_REAL_MIN: i128 = 200000000000000;
 const P1_REAL_MAX: i128 = 400000000000000;
 
-fn main() {
-    let args = Cli::parse();
+pub struct Day24;
 
-    let inp = fs::read_to_string(args.input).expect("can't open input file");
+impl Solution for Day24 {
+    type Input<'a> = Vec<Hailstone>;
+    type Answer1 = usize;
+    type Answer2 = i128;
+
+    fn parse<'a>(&self, raw_inp: &'a str) -> Result<Self::Input<'a>, ParseError> {
+        parse(raw_inp)
+    }
+
+    fn part1(&self, input: &Self::Input<'_>) -> usize {
+        calculate_p1::<P1_REAL_MIN, P1_REAL_MAX>(input)
+    }
 
-    let data = parse(&inp);
-    let p1 = calculate_p1::<P1_REAL_MIN, P1_REAL_MAX>(&data);
-    let p2 = calculate_p2(&data);
-    println!("{}\n{}", p1, p2);
+    fn part2(&self, input: &Self::Input<'_>) -> Option<i128> {
+        Some(calculate_p2(input))
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    const EXAMPLE_DATA: &str = include_str!("../../inputs/examples/2023_24");
-    const REAL_DATA: &str = include_str!("../../inputs/real/2023_24");
-
-    #[test]
-    fn test_p1_example() {
-        assert_eq!(calculate_p1::<7, 27>(&parse(EXAMPLE_DATA)), 2);
-    }
-
-    #[test]
-    fn test_p2_example() {
-        assert_eq!(calculate_p2(&parse(EXAMPLE_DATA)), 47);
-    }
+    aoc_tests! {
+        real_input: "2023_24";
 
-    #[test]
-    fn test_p1_real() {
-        assert_eq!(
-            calculate_p1::<P1_REAL_MIN, P1_REAL_MAX>(&parse(REAL_DATA)),
-            17906
-        );
-    }
+        p1_example_area: |inp| calculate_p1::<7, 27>(&parse(inp).unwrap()) => {
+            example("2023_24") => 2,
+        }
 
-    #[test]
-    fn test_p2_real() {
-        assert_eq!(calculate_p2(&parse(REAL_DATA)), 571093786416929);
-    }
+        p1 (part 1): |inp| calculate_p1::<P1_REAL_MIN, P1_REAL_MAX>(&parse(inp).unwrap()) => {}
 
-    #[cfg(feature = "bench")]
-    mod benches {
-        extern crate test;
-        use test::{black_box, Bencher};
-
-        use super::*;
-
-        #[bench]
-        fn bench(b: &mut Bencher) {
-            b.iter(|| {
-                let data = parse(black_box(REAL_DATA));
-                let p1 = calculate_p1::<P1_REAL_MIN, P1_REAL_MAX>(&data);
-                let p2 = calculate_p2(&data);
-                (p1, p2)
-            });
+        p2 (part 2): |inp| calculate_p2(&parse(inp).unwrap()) => {
+            example("2023_24") => 47,
         }
     }
 }
@@ -0,0 +1,173 @@
+use crate::graphviz::DotGraph;
+use crate::parse_util::{split_once, ParseError};
+use crate::Solution;
+use ahash::{AHashMap, AHashSet};
+use std::convert::Infallible;
+
+// One side of a cut of 3 edges, or None if the first vertex picked was unlucky
+fn dodgy_min_cut<'a>(edges: &[(&'a str, &'a str)]) -> Option<AHashSet<&'a str>> {
+    let mut vertices = AHashSet::<&str>::default();
+
+    for (a, b) in edges {
+        vertices.insert(a);
+        vertices.insert(b);
+    }
+
+    let mut graph = AHashSet::<&str>::default();
+    graph.insert(vertices.iter().next()?);
+
+    while graph.len() < vertices.len().saturating_sub(3) {
+        let mut candidate_vertices = AHashMap::<&str, usize>::default();
+        for (a, b) in edges.iter() {
+            // If there's an edge linking any vertex in the graph so far to any vertex
+            // outside the graph...
+            let a_in_graph = graph.contains(a);
+            let b_in_graph = graph.contains(b);
+            if a_in_graph != b_in_graph {
+                if !a_in_graph {
+                    *candidate_vertices.entry(a).or_insert(0) += 1;
+                }
+                if !b_in_graph {
+                    *candidate_vertices.entry(b).or_insert(0) += 1;
+                }
+            }
+        }
+
+        if candidate_vertices.values().sum::<usize>() == 3 {
+            return Some(graph);
+        }
+
+        // Nothing left to grow into if the graph is already split
+        let &vertex = candidate_vertices
+            .iter()
+            .max_by(|a, b| a.1.cmp(b.1))
+            .map(|(k, _)| k)?;
+
+        graph.insert(vertex);
+    }
+    None
+}
+
+pub fn parse(raw_inp: &str) -> Result<Vec<(&str, &str)>, ParseError> {
+    let mut edges = vec![];
+
+    for line in raw_inp.trim().lines() {
+        let (k, v) = split_once(line, ": ")?;
+        edges.extend(v.split(' ').map(|v| (k, v)));
+    }
+
+    Ok(edges)
+}
+
+/// The three wires which split the components into two groups.
+pub struct Cut<'a> {
+    pub wires: Vec<(&'a str, &'a str)>,
+    pub sizes: (usize, usize),
+}
+
+// Each attempt starts from a vertex picked by hash order, which is random
+const MAX_ATTEMPTS: usize = 1000;
+
+/// Finds three wires which split the components into two groups, or `None`
+/// if none turn up after many attempts - most likely because there aren't any.
+pub fn find_cut<'a>(data: &[(&'a str, &'a str)]) -> Option<Cut<'a>> {
+    // Ok this is horribly hacky but we can literally just retry on failure and hope we pick a first
+    // 3 nodes that happen to all be on the same side of the graph. Once that's true, it will work...
+    let side = (0..MAX_ATTEMPTS).find_map(|_| dodgy_min_cut(data))?;
+
+    let vertices = data
+        .iter()
+        .flat_map(|&(a, b)| [a, b])
+        .collect::<AHashSet<_>>()
+        .len();
+
+    Some(Cut {
+        wires: data
+            .iter()
+            .filter(|(a, b)| side.contains(a) != side.contains(b))
+            .copied()
+            .collect(),
+        sizes: (side.len(), vertices - side.len()),
+    })
+}
+
+fn calculate(data: &[(&str, &str)]) -> Result<usize, String> {
+    let cut = find_cut(data).ok_or_else(|| "can't find 3 wires to cut".to_string())?;
+    Ok(cut.sizes.0 * cut.sizes.1)
+}
+
+/// The wiring as a Graphviz graph, with the wires of `cut` highlighted.
+pub fn to_dot(data: &[(&str, &str)], cut: Option<&Cut>) -> DotGraph {
+    let mut graph = DotGraph::undirected();
+    for &(a, b) in data {
+        graph.edge(a, b, None);
+    }
+    if let Some(cut) = cut {
+        for &(a, b) in &cut.wires {
+            graph.highlight_edge(a, b);
+        }
+    }
+    graph
+}
+
+pub struct Day25;
+
+impl Solution for Day25 {
+    type Input<'a> = Vec<(&'a str, &'a str)>;
+    // The product of the group sizes, or an error if there's no cut
+    type Answer1 = Result<usize, String>;
+    // Day 25 has no part 2
+    type Answer2 = Infallible;
+
+    fn parse<'a>(&self, raw_inp: &'a str) -> Result<Self::Input<'a>, ParseError> {
+        parse(raw_inp)
+    }
+
+    fn part1(&self, input: &Self::Input<'_>) -> Result<usize, String> {
+        calculate(input)
+    }
+
+    fn has_part2(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    aoc_tests! {
+        real_input: "2023_25";
+
+        p1 (part 1): |inp| calculate(&parse(inp).unwrap()).unwrap() => {
+            example("2023_25") => 54,
+        }
+    }
+
+    #[test]
+    fn test_find_cut() {
+        let example = fixture!("2023_25");
+        let data = parse(example).unwrap();
+        let cut = find_cut(&data).unwrap();
+
+        let mut wires = cut
+            .wires
+            .iter()
+            .map(|&(a, b)| if a < b { (a, b) } else { (b, a) })
+            .collect::<Vec<_>>();
+        wires.sort();
+        assert_eq!(wires, [("bvb", "cmg"), ("hfx", "pzl"), ("jqt", "nvd")]);
+        assert_eq!(cut.sizes.0 * cut.sizes.1, 54);
+
+        let dot = to_dot(&data, Some(&cut)).to_string();
+        assert_eq!(dot.matches("color=red").count(), 3);
+
+        // A triangle can't be split by cutting 3 wires
+        assert!(find_cut(&[("a", "b"), ("b", "c"), ("c", "a")]).is_none());
+        assert!(find_cut(&[]).is_none());
+        assert_eq!(
+            calculate(&[("a", "b"), ("b", "c"), ("c", "a")]),
+            Err("can't find 3 wires to cut".to_string())
+        );
+    }
+}
@@ -0,0 +1,154 @@
+use ahash::{AHashMap, AHashSet};
+use std::fmt;
+
+/// A graph to write in Graphviz's DOT format, e.g. to render with
+/// `dot -Tsvg graph.dot > graph.svg`.
+///
+/// Nodes can have a kind, drawn with its own shape and shown as a tooltip, and
+/// edges a weight, drawn as a label. Highlighted nodes and edges are drawn in red.
+#[derive(Clone, Debug, Default)]
+pub struct DotGraph {
+    directed: bool,
+    shapes: AHashMap<String, String>,
+    nodes: Vec<(String, Option<String>)>,
+    edges: Vec<(String, String, Option<usize>)>,
+    highlighted_nodes: AHashSet<String>,
+    highlighted_edges: AHashSet<(String, String)>,
+}
+
+impl DotGraph {
+    /// A graph whose edges go one way, drawn with arrows.
+    pub fn directed() -> DotGraph {
+        DotGraph {
+            directed: true,
+            ..Default::default()
+        }
+    }
+
+    pub fn undirected() -> DotGraph {
+        DotGraph::default()
+    }
+
+    /// Draws nodes of `kind` as `shape`, e.g. `box` or `diamond`, rather than
+    /// the default ellipse.
+    pub fn shape(&mut self, kind: &str, shape: &str) {
+        self.shapes.insert(kind.to_owned(), shape.to_owned());
+    }
+
+    /// Adds a node, which is only needed for nodes with a kind or without any
+    /// edges - the ends of an edge are added automatically.
+    pub fn node(&mut self, name: impl Into<String>, kind: Option<&str>) {
+        self.nodes.push((name.into(), kind.map(str::to_owned)));
+    }
+
+    pub fn edge(&mut self, from: impl Into<String>, to: impl Into<String>, weight: Option<usize>) {
+        self.edges.push((from.into(), to.into(), weight));
+    }
+
+    pub fn highlight_node(&mut self, name: impl Into<String>) {
+        self.highlighted_nodes.insert(name.into());
+    }
+
+    /// Highlights the edge from `from` to `to`, or either way round in an
+    /// undirected graph.
+    pub fn highlight_edge(&mut self, from: impl Into<String>, to: impl Into<String>) {
+        self.highlighted_edges.insert((from.into(), to.into()));
+    }
+
+    fn is_highlighted(&self, from: &str, to: &str) -> bool {
+        let key = |a: &str, b: &str| (a.to_owned(), b.to_owned());
+        self.highlighted_edges.contains(&key(from, to))
+            || (!self.directed && self.highlighted_edges.contains(&key(to, from)))
+    }
+}
+
+// Quotes an ID, so that names needn't be valid DOT identifiers
+fn quote(s: &str) -> String {
+    format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+fn write_attributes(f: &mut fmt::Formatter, attributes: &[String]) -> fmt::Result {
+    if attributes.is_empty() {
+        writeln!(f, ";")
+    } else {
+        writeln!(f, " [{}];", attributes.join(", "))
+    }
+}
+
+const HIGHLIGHT: &str = "color=red, penwidth=3";
+
+impl fmt::Display for DotGraph {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (keyword, arrow) = if self.directed {
+            ("digraph", "->")
+        } else {
+            ("graph", "--")
+        };
+        writeln!(f, "{} {{", keyword)?;
+
+        for (name, kind) in &self.nodes {
+            let mut attributes = vec![];
+            if let Some(kind) = kind {
+                if let Some(shape) = self.shapes.get(kind) {
+                    attributes.push(format!("shape={}", shape));
+                }
+                attributes.push(format!("tooltip={}", quote(kind)));
+            }
+            if self.highlighted_nodes.contains(name) {
+                attributes.push(HIGHLIGHT.to_owned());
+            }
+            write!(f, "    {}", quote(name))?;
+            write_attributes(f, &attributes)?;
+        }
+
+        for (from, to, weight) in &self.edges {
+            let mut attributes = vec![];
+            if let Some(weight) = weight {
+                attributes.push(format!("label={}", quote(&weight.to_string())));
+            }
+            if self.is_highlighted(from, to) {
+                attributes.push(HIGHLIGHT.to_owned());
+            }
+            write!(f, "    {} {} {}", quote(from), arrow, quote(to))?;
+            write_attributes(f, &attributes)?;
+        }
+
+        writeln!(f, "}}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dot() {
+        let mut graph = DotGraph::directed();
+        graph.shape("flip-flop", "box");
+        graph.node("a", Some("flip-flop"));
+        graph.node("b\"c", None);
+        graph.edge("a", "b\"c", Some(12));
+        graph.edge("b\"c", "a", None);
+        graph.highlight_node("a");
+        graph.highlight_edge("a", "b\"c");
+
+        assert_eq!(
+            graph.to_string(),
+            r#"digraph {
+    "a" [shape=box, tooltip="flip-flop", color=red, penwidth=3];
+    "b\"c";
+    "a" -> "b\"c" [label="12", color=red, penwidth=3];
+    "b\"c" -> "a";
+}
+"#
+        );
+
+        let mut graph = DotGraph::undirected();
+        graph.edge("x", "y", None);
+        graph.highlight_edge("y", "x");
+        assert_eq!(
+            graph.to_string(),
+            "graph {\n    \"x\" -- \"y\" [color=red, penwidth=3];\n}\n"
+        );
+    }
+}
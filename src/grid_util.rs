@@ -1,33 +1,516 @@
+use crate::parse_util::ParseError;
+use ahash::AHashSet;
 use ndarray::Array2;
+use std::ops::{Index, IndexMut};
+
+/// Parses a rectangular grid of ASCII cells, mapping each cell with `f`.
+///
+/// `expected` describes the valid cells, for errors where `f` returns `None`.
+pub fn make_grid<T>(
+    raw_inp: &str,
+    expected: &str,
+    f: impl Fn(u8) -> Option<T>,
+) -> Result<Array2<T>, ParseError> {
+    let raw_inp = raw_inp.trim();
 
-pub fn make_byte_grid(raw_inp: &str) -> Array2<u8> {
     let columns = raw_inp
-        .trim()
-        .bytes()
-        .position(|c| c == b'\n')
-        .expect("can't get column count");
+        .lines()
+        .next()
+        .map(|line| line.len())
+        .filter(|&columns| columns > 0)
+        .ok_or_else(|| ParseError::new(raw_inp, "a grid"))?;
+
+    let mut cells = Vec::with_capacity(raw_inp.len());
+
+    for line in raw_inp.lines() {
+        // Each cell is one byte, so errors can point at any cell
+        if let Some(idx) = line.find(|c: char| !c.is_ascii()) {
+            return Err(ParseError::new(&line[idx..], expected));
+        }
+        if line.len() != columns {
+            return Err(ParseError::new(
+                &line[line.len().min(columns)..],
+                format!("{} columns", columns),
+            ));
+        }
+
+        for (idx, b) in line.bytes().enumerate() {
+            cells.push(f(b).ok_or_else(|| ParseError::new(&line[idx..], expected))?);
+        }
+    }
 
-    Array2::from_shape_vec(
-        ((raw_inp.trim().len() + 1) / (columns + 1), columns),
-        raw_inp.bytes().filter(|&x| x != b'\n').collect(),
-    )
-    .expect("can't make array")
+    Ok(Array2::from_shape_vec((cells.len() / columns, columns), cells).expect("can't make array"))
 }
 
-pub fn make_bool_grid<const TRUE_CHAR: u8>(raw_inp: &str) -> Array2<bool> {
-    let columns = raw_inp
-        .trim()
-        .bytes()
-        .position(|c| c == b'\n')
-        .expect("can't get column count");
-
-    Array2::from_shape_vec(
-        ((raw_inp.trim().len() + 1) / (columns + 1), columns),
-        raw_inp
-            .bytes()
-            .filter(|&x| x != b'\n')
-            .map(|b| b == TRUE_CHAR)
-            .collect(),
-    )
-    .expect("can't make array")
+/// Parses a grid of bytes, each of which must be one of `tiles`.
+pub fn make_tile_grid(raw_inp: &str, tiles: &str) -> Result<Array2<u8>, ParseError> {
+    make_grid(raw_inp, &format!("a tile (one of {})", tiles), |b| {
+        tiles.as_bytes().contains(&b).then_some(b)
+    })
+}
+
+pub fn make_bool_grid<const TRUE_CHAR: u8>(raw_inp: &str) -> Result<Array2<bool>, ParseError> {
+    make_grid(raw_inp, "an ASCII character", |b| Some(b == TRUE_CHAR))
+}
+
+/// Creates an error pointing at grid cell `pos` of a grid parsed from `raw_inp`.
+pub fn grid_error(raw_inp: &str, pos: (usize, usize), expected: &str) -> ParseError {
+    let line = raw_inp.trim().lines().nth(pos.0).unwrap_or_default();
+    ParseError::new(&line[pos.1.min(line.len())..], expected)
+}
+
+/// A position in a grid, as a row and column.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct Pos {
+    pub y: usize,
+    pub x: usize,
+}
+
+impl Pos {
+    pub const fn new(y: usize, x: usize) -> Pos {
+        Pos { y, x }
+    }
+
+    /// Offsets the position, or returns `None` if it would go negative.
+    pub fn checked_offset(self, dy: isize, dx: isize) -> Option<Pos> {
+        Some(Pos {
+            y: self.y.checked_add_signed(dy)?,
+            x: self.x.checked_add_signed(dx)?,
+        })
+    }
+
+    /// Offsets the position, wrapping around `usize`. Positions that went
+    /// negative end up far outside any grid, so lookups on them return `None`.
+    pub fn wrapping_offset(self, dy: isize, dx: isize) -> Pos {
+        Pos {
+            y: self.y.wrapping_add_signed(dy),
+            x: self.x.wrapping_add_signed(dx),
+        }
+    }
+
+    pub fn step(self, dir: Dir) -> Option<Pos> {
+        let (dy, dx) = dir.delta();
+        self.checked_offset(dy, dx)
+    }
+
+    pub fn wrapping_step(self, dir: Dir) -> Pos {
+        let (dy, dx) = dir.delta();
+        self.wrapping_offset(dy, dx)
+    }
+}
+
+impl From<(usize, usize)> for Pos {
+    fn from((y, x): (usize, usize)) -> Pos {
+        Pos { y, x }
+    }
+}
+
+impl From<Pos> for (usize, usize) {
+    fn from(pos: Pos) -> (usize, usize) {
+        (pos.y, pos.x)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub enum Dir {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Dir {
+    /// All directions, clockwise from `Up`.
+    pub const ALL: [Dir; 4] = [Dir::Up, Dir::Right, Dir::Down, Dir::Left];
+
+    /// The change in (row, column) from moving one step in this direction.
+    pub const fn delta(self) -> (isize, isize) {
+        match self {
+            Dir::Up => (-1, 0),
+            Dir::Right => (0, 1),
+            Dir::Down => (1, 0),
+            Dir::Left => (0, -1),
+        }
+    }
+
+    /// Index of this direction in `Dir::ALL`, for use in lookup tables.
+    pub const fn index(self) -> usize {
+        self as usize
+    }
+
+    pub const fn reverse(self) -> Dir {
+        Dir::ALL[(self.index() + 2) % 4]
+    }
+
+    pub const fn turn_right(self) -> Dir {
+        Dir::ALL[(self.index() + 1) % 4]
+    }
+
+    pub const fn turn_left(self) -> Dir {
+        Dir::ALL[(self.index() + 3) % 4]
+    }
+}
+
+const NEIGHBOURS_8: [(isize, isize); 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, -1),
+    (0, 1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+];
+
+/// A rectangular grid, indexed by `Pos`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Grid<T> {
+    cells: Array2<T>,
+}
+
+impl Grid<u8> {
+    /// Parses a grid; see `make_tile_grid`.
+    pub fn parse_tiles(raw_inp: &str, tiles: &str) -> Result<Grid<u8>, ParseError> {
+        make_tile_grid(raw_inp, tiles).map(Grid::new)
+    }
+}
+
+impl<T> Grid<T> {
+    pub fn new(cells: Array2<T>) -> Grid<T> {
+        Grid { cells }
+    }
+
+    pub fn from_elem(dims: (usize, usize), elem: T) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid::new(Array2::from_elem(dims, elem))
+    }
+
+    /// Parses a grid; see `make_grid`.
+    pub fn parse(
+        raw_inp: &str,
+        expected: &str,
+        f: impl Fn(u8) -> Option<T>,
+    ) -> Result<Grid<T>, ParseError> {
+        make_grid(raw_inp, expected, f).map(Grid::new)
+    }
+
+    /// Number of (rows, columns).
+    pub fn dim(&self) -> (usize, usize) {
+        self.cells.dim()
+    }
+
+    pub fn rows(&self) -> usize {
+        self.cells.dim().0
+    }
+
+    pub fn columns(&self) -> usize {
+        self.cells.dim().1
+    }
+
+    pub fn contains(&self, pos: Pos) -> bool {
+        pos.y < self.rows() && pos.x < self.columns()
+    }
+
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        self.cells.get((pos.y, pos.x))
+    }
+
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        self.cells.get_mut((pos.y, pos.x))
+    }
+
+    /// Offsets `pos`, or returns `None` if it would leave the grid.
+    pub fn checked_offset(&self, pos: Pos, dy: isize, dx: isize) -> Option<Pos> {
+        pos.checked_offset(dy, dx).filter(|&p| self.contains(p))
+    }
+
+    /// Offsets `pos`, wrapping around the edges of the grid as if it were
+    /// tiled infinitely in every direction.
+    pub fn wrapping_offset(&self, pos: Pos, dy: isize, dx: isize) -> Pos {
+        let (rows, columns) = self.dim();
+        Pos {
+            y: (pos.y as isize + dy).rem_euclid(rows as isize) as usize,
+            x: (pos.x as isize + dx).rem_euclid(columns as isize) as usize,
+        }
+    }
+
+    /// Moves one step from `pos`, or returns `None` if it would leave the grid.
+    pub fn step(&self, pos: Pos, dir: Dir) -> Option<Pos> {
+        pos.step(dir).filter(|&p| self.contains(p))
+    }
+
+    /// Orthogonal neighbours of `pos` which are inside the grid, in the order
+    /// of `Dir::ALL`.
+    pub fn neighbours4(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        Dir::ALL
+            .into_iter()
+            .filter_map(move |dir| self.step(pos, dir))
+    }
+
+    /// Orthogonal and diagonal neighbours of `pos` which are inside the grid,
+    /// row by row.
+    pub fn neighbours8(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        NEIGHBOURS_8
+            .into_iter()
+            .filter_map(move |(dy, dx)| self.checked_offset(pos, dy, dx))
+    }
+
+    /// Iterates over every cell in row-major order.
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.cells
+            .indexed_iter()
+            .map(|((y, x), cell)| (Pos { y, x }, cell))
+    }
+
+    /// Position of the first cell, in row-major order, matching `pred`.
+    pub fn find(&self, pred: impl Fn(&T) -> bool) -> Option<Pos> {
+        self.iter().find(|(_, cell)| pred(cell)).map(|(pos, _)| pos)
+    }
+
+    /// Positions of every cell equal to `value`, in row-major order.
+    pub fn positions_of<'a>(&'a self, value: &'a T) -> impl Iterator<Item = Pos> + 'a
+    where
+        T: PartialEq,
+    {
+        self.iter()
+            .filter(move |(_, cell)| *cell == value)
+            .map(|(pos, _)| pos)
+    }
+
+    pub fn as_array(&self) -> &Array2<T> {
+        &self.cells
+    }
+
+    pub fn into_array(self) -> Array2<T> {
+        self.cells
+    }
+}
+
+impl<T> From<Array2<T>> for Grid<T> {
+    fn from(cells: Array2<T>) -> Grid<T> {
+        Grid::new(cells)
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &T {
+        &self.cells[(pos.y, pos.x)]
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut T {
+        &mut self.cells[(pos.y, pos.x)]
+    }
+}
+
+/// Output format for `Solution::render`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RenderFormat {
+    Text,
+    Ppm,
+}
+
+impl RenderFormat {
+    /// Picks a format from a file name: PPM for `.ppm`, otherwise text.
+    pub fn from_path(path: &str) -> RenderFormat {
+        if path.to_ascii_lowercase().ends_with(".ppm") {
+            RenderFormat::Ppm
+        } else {
+            RenderFormat::Text
+        }
+    }
+}
+
+pub type Rgb = [u8; 3];
+
+/// Positions to draw on top of a rendered grid, replacing their cells.
+pub struct Overlay<'a> {
+    pub positions: &'a AHashSet<Pos>,
+    pub symbol: char,
+    pub colour: Rgb,
+}
+
+impl Overlay<'_> {
+    fn get(overlay: Option<&Self>, pos: Pos) -> Option<&Self> {
+        overlay.filter(|o| o.positions.contains(&pos))
+    }
+}
+
+/// Renders a grid as text, one line per row, with `palette` giving the
+/// character for each cell.
+pub fn render_text<T>(
+    grid: &Grid<T>,
+    palette: impl Fn(&T) -> char,
+    overlay: Option<&Overlay>,
+) -> String {
+    let mut result = String::with_capacity(grid.rows() * (grid.columns() + 1));
+
+    for (pos, cell) in grid.iter() {
+        match Overlay::get(overlay, pos) {
+            Some(o) => result.push(o.symbol),
+            None => result.push(palette(cell)),
+        }
+        if pos.x == grid.columns() - 1 {
+            result.push('\n');
+        }
+    }
+
+    result
+}
+
+/// Renders a grid as a binary PPM image, with `palette` giving the colour of
+/// each cell and each cell drawn as a `scale` by `scale` square.
+pub fn render_ppm<T>(
+    grid: &Grid<T>,
+    scale: usize,
+    palette: impl Fn(&T) -> Rgb,
+    overlay: Option<&Overlay>,
+) -> Vec<u8> {
+    let scale = scale.max(1);
+    let (width, height) = (grid.columns() * scale, grid.rows() * scale);
+
+    let mut result = format!("P6\n{} {}\n255\n", width, height).into_bytes();
+    result.reserve(width * height * 3);
+
+    for y in 0..grid.rows() {
+        let row = (0..grid.columns())
+            .map(|x| Pos { y, x })
+            .map(|pos| match Overlay::get(overlay, pos) {
+                Some(o) => o.colour,
+                None => palette(&grid[pos]),
+            })
+            .flat_map(|colour| std::iter::repeat_n(colour, scale))
+            .flatten()
+            .collect::<Vec<_>>();
+
+        for _ in 0..scale {
+            result.extend_from_slice(&row);
+        }
+    }
+
+    result
+}
+
+/// Size, in pixels, of each cell when `render` produces an image.
+const RENDER_SCALE: usize = 4;
+
+/// Renders a grid in `format`, using `symbol` for text and `colour` for images.
+pub fn render<T>(
+    grid: &Grid<T>,
+    format: RenderFormat,
+    symbol: impl Fn(&T) -> char,
+    colour: impl Fn(&T) -> Rgb,
+    overlay: Option<&Overlay>,
+) -> Vec<u8> {
+    match format {
+        RenderFormat::Text => render_text(grid, symbol, overlay).into_bytes(),
+        RenderFormat::Ppm => render_ppm(grid, RENDER_SCALE, colour, overlay),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid() -> Grid<u8> {
+        Grid::parse("ab.\n.#b\n", "", Some).unwrap()
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let location = |inp: &str| {
+            let err = make_tile_grid(inp, "#.").unwrap_err().locate(inp);
+            let loc = err.location().unwrap();
+            (loc.line, loc.column)
+        };
+
+        assert_eq!(location("#.\n.#.\n"), (2, 3));
+        assert_eq!(location("#.\n.\n"), (2, 2));
+        assert_eq!(location("#.\n#x\n"), (2, 2));
+        // Multibyte characters aren't cells, even where a line has too many bytes
+        assert_eq!(location("#.\n.é\n"), (2, 2));
+        assert_eq!(location("é.\n..\n"), (1, 1));
+        assert!(make_bool_grid::<b'#'>("#.\n.é\n").is_err());
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let grid = grid();
+
+        let corner = grid.neighbours4(Pos::new(0, 0)).collect::<Vec<_>>();
+        assert_eq!(corner, vec![Pos::new(0, 1), Pos::new(1, 0)]);
+
+        let middle = grid.neighbours8(Pos::new(0, 1)).collect::<Vec<_>>();
+        assert_eq!(
+            middle,
+            vec![
+                Pos::new(0, 0),
+                Pos::new(0, 2),
+                Pos::new(1, 0),
+                Pos::new(1, 1),
+                Pos::new(1, 2)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_offsets() {
+        let grid = grid();
+        let pos = Pos::new(0, 2);
+
+        assert_eq!(grid.step(pos, Dir::Right), None);
+        assert_eq!(grid.step(pos, Dir::Down), Some(Pos::new(1, 2)));
+        assert_eq!(grid.checked_offset(pos, -1, 0), None);
+        assert_eq!(grid.wrapping_offset(pos, -1, 1), Pos::new(1, 0));
+        assert_eq!(grid.wrapping_offset(pos, 5, -7), Pos::new(1, 1));
+        assert_eq!(grid.get(pos.wrapping_step(Dir::Up)), None);
+
+        assert_eq!(Dir::Up.reverse(), Dir::Down);
+        assert_eq!(Dir::Left.turn_right(), Dir::Up);
+        assert_eq!(Dir::Up.turn_left(), Dir::Left);
+    }
+
+    #[test]
+    fn test_find() {
+        let grid = grid();
+
+        assert_eq!(grid.find(|&c| c == b'#'), Some(Pos::new(1, 1)));
+        assert_eq!(grid.find(|&c| c == b'z'), None);
+        assert_eq!(
+            grid.positions_of(&b'.').collect::<Vec<_>>(),
+            vec![Pos::new(0, 2), Pos::new(1, 0)]
+        );
+        assert_eq!(grid[Pos::new(1, 2)], b'b');
+    }
+
+    #[test]
+    fn test_render() {
+        let grid = grid();
+        let highlight = AHashSet::from_iter([Pos::new(0, 1)]);
+        let overlay = Overlay {
+            positions: &highlight,
+            symbol: '*',
+            colour: [255, 0, 0],
+        };
+
+        let text = render_text(&grid, |&c| c.to_ascii_uppercase() as char, Some(&overlay));
+        assert_eq!(text, "A*.\n.#B\n");
+
+        let ppm = render_ppm(&grid, 2, |&c| [c; 3], Some(&overlay));
+        let pixels = ppm
+            .strip_prefix(b"P6\n6 4\n255\n")
+            .expect("should have a PPM header");
+        assert_eq!(pixels.len(), 6 * 4 * 3);
+        // Second cell of the first row is highlighted, and drawn 2 pixels wide
+        assert_eq!(
+            &pixels[..12],
+            &[b'a', b'a', b'a', b'a', b'a', b'a', 255, 0, 0, 255, 0, 0]
+        );
+        assert_eq!(&pixels[pixels.len() - 6..], &[b'b'; 6]);
+    }
 }
@@ -0,0 +1,171 @@
+use std::ops::{Add, Range};
+
+/// A set of values stored as sorted, disjoint, half-open ranges.
+///
+/// Ranges which overlap or touch are merged, so each set has exactly one
+/// representation and sets can be compared with `==`.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct IntervalSet<T> {
+    ranges: Vec<Range<T>>,
+}
+
+impl<T: Copy + Ord> IntervalSet<T> {
+    pub fn new() -> IntervalSet<T> {
+        IntervalSet { ranges: vec![] }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// The disjoint ranges making up the set, in ascending order.
+    pub fn ranges(&self) -> &[Range<T>] {
+        &self.ranges
+    }
+
+    /// Smallest value in the set.
+    pub fn min(&self) -> Option<T> {
+        self.ranges.first().map(|r| r.start)
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        // First range which ends after value is the only one which could contain it
+        let idx = self.ranges.partition_point(|r| r.end <= value);
+        self.ranges.get(idx).is_some_and(|r| r.start <= value)
+    }
+
+    pub fn insert(&mut self, range: Range<T>) {
+        *self = self.union(&IntervalSet::from(range));
+    }
+
+    pub fn union(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        self.ranges
+            .iter()
+            .chain(other.ranges.iter())
+            .cloned()
+            .collect()
+    }
+
+    pub fn intersection(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        let mut ranges = vec![];
+        let (mut a, mut b) = (0, 0);
+
+        while let (Some(x), Some(y)) = (self.ranges.get(a), other.ranges.get(b)) {
+            let start = x.start.max(y.start);
+            let end = x.end.min(y.end);
+            if start < end {
+                ranges.push(start..end);
+            }
+
+            // Whichever range ends first can't overlap anything else
+            if x.end < y.end {
+                a += 1;
+            } else {
+                b += 1;
+            }
+        }
+
+        IntervalSet { ranges }
+    }
+
+    /// Values in `self` but not in `other`.
+    pub fn difference(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        let mut ranges = vec![];
+        let mut b = 0;
+
+        for x in self.ranges.iter() {
+            let mut start = x.start;
+
+            // Skip ranges entirely before this one - they can't affect later ones either
+            while other.ranges.get(b).is_some_and(|y| y.end <= start) {
+                b += 1;
+            }
+
+            for y in other.ranges[b..].iter().take_while(|y| y.start < x.end) {
+                if y.start > start {
+                    ranges.push(start..y.start);
+                }
+                start = start.max(y.end);
+            }
+
+            if start < x.end {
+                ranges.push(start..x.end);
+            }
+        }
+
+        IntervalSet { ranges }
+    }
+
+    /// Adds `offset` to every value in the set.
+    pub fn shift<O>(&self, offset: O) -> IntervalSet<T>
+    where
+        T: Add<O, Output = T>,
+        O: Copy,
+    {
+        IntervalSet {
+            ranges: self
+                .ranges
+                .iter()
+                .map(|r| r.start + offset..r.end + offset)
+                .collect(),
+        }
+    }
+}
+
+impl<T: Copy + Ord> From<Range<T>> for IntervalSet<T> {
+    fn from(range: Range<T>) -> IntervalSet<T> {
+        std::iter::once(range).collect()
+    }
+}
+
+impl<T: Copy + Ord> FromIterator<Range<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = Range<T>>>(iter: I) -> IntervalSet<T> {
+        let mut sorted = iter
+            .into_iter()
+            .filter(|r| r.start < r.end)
+            .collect::<Vec<_>>();
+        sorted.sort_unstable_by_key(|r| r.start);
+
+        let mut ranges: Vec<Range<T>> = Vec::with_capacity(sorted.len());
+        for r in sorted {
+            match ranges.last_mut() {
+                Some(last) if r.start <= last.end => last.end = last.end.max(r.end),
+                _ => ranges.push(r),
+            }
+        }
+
+        IntervalSet { ranges }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalise() {
+        let set: IntervalSet<i64> = [5..8, 0..2, 2..3, 7..10, 4..4].into_iter().collect();
+        assert_eq!(set.ranges(), &[0..3, 5..10]);
+        assert_eq!(set.min(), Some(0));
+
+        assert!(set.contains(2));
+        assert!(!set.contains(3));
+        assert!(set.contains(9));
+        assert!(!set.contains(10));
+    }
+
+    #[test]
+    fn test_set_operations() {
+        let a: IntervalSet<i64> = [0..10, 20..30].into_iter().collect();
+        let b: IntervalSet<i64> = [5..25, 28..29, 40..50].into_iter().collect();
+
+        assert_eq!(a.union(&b).ranges(), &[0..30, 40..50]);
+        assert_eq!(a.intersection(&b).ranges(), &[5..10, 20..25, 28..29]);
+        assert_eq!(a.difference(&b).ranges(), &[0..5, 25..28, 29..30]);
+        assert_eq!(b.difference(&a).ranges(), &[10..20, 40..50]);
+        assert_eq!(a.shift(-5).ranges(), &[-5..5, 15..25]);
+
+        assert!(a.difference(&a).is_empty());
+        assert!(a.intersection(&IntervalSet::new()).is_empty());
+    }
+}
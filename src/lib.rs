@@ -1,8 +1,480 @@
+use bench::BenchOptions;
+use clap::builder::RangedU64ValueParser;
 pub use clap::Parser;
+use clap::{Args, Subcommand, ValueEnum};
+use grid_util::Pos;
+use std::ops::{Range, RangeInclusive};
+use std::str::FromStr;
+pub mod bench;
+#[cfg(test)]
+#[macro_use]
+mod test_util;
+pub mod days;
+pub mod graphviz;
 pub mod grid_util;
+pub mod interval_set;
+pub mod nonogram;
+pub mod parse_util;
+pub mod pathfind;
+mod solution;
+pub mod verify;
+pub use solution::{Answer, BenchResult, Day, DayResult, PartResult, Solution};
 
 #[derive(Parser)]
+#[clap(name = "aoc")]
 pub struct Cli {
+    #[clap(subcommand)]
+    pub command: Command,
+
+    /// Output format for answers
+    #[clap(long, value_enum, global = true, default_value_t = Format::Text)]
+    pub format: Format,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, ValueEnum)]
+pub enum Format {
+    /// One answer per line
+    Text,
+    /// A JSON array with one object per part, including timings
+    Json,
+}
+
+#[derive(Subcommand)]
+pub enum Command {
+    /// Run the selected days and print their answers
+    Run(RunArgs),
+    /// Check answers against each input's manifest (`<input>.answers`)
+    Verify(VerifyArgs),
+    /// Trace values in the day 5 almanac back to the seeds which produce them
+    Almanac(AlmanacArgs),
+    /// List the boxes of ratings accepted by the day 19 workflows, check parts against them, or
+    /// lint the workflows
+    Workflows(WorkflowsArgs),
+    /// List the concrete arrangements of day 12 spring records, or sample them at random
+    Springs(SpringsArgs),
+    /// Solve a nonogram by treating its rows and columns as day 12 records
+    Nonogram(NonogramArgs),
+    /// Find the day 17 crucible route with the least heat loss under custom movement rules
+    Crucible(CrucibleArgs),
+    /// Press the button on a day 20 module network, with statistics and a trace of every pulse
+    Pulses(PulsesArgs),
+    /// Write the day 20 module network, day 23 trails or day 25 wiring as a Graphviz DOT graph;
+    /// `--format json` isn't supported
+    Graph(GraphArgs),
+}
+
+#[derive(Args)]
+pub struct RunArgs {
+    /// Days to run: a single day (`17`), a range (`5..12`, `5..=12`) or `all`
+    #[clap(required = true)]
+    pub days: Vec<DaySelection>,
+
+    /// Input files to use instead of the default, or `-` for stdin; only valid
+    /// for a single day. Can be given several times.
     #[clap(short, long)]
+    pub input: Vec<String>,
+
+    /// Directory containing inputs named `2023_01`, `2023_02`, ...
+    #[clap(long, default_value = "inputs/real")]
+    pub inputs_dir: String,
+
+    /// Benchmark parsing and each part over N timed runs instead of printing answers
+    #[clap(long, value_name = "N", value_parser = RangedU64ValueParser::<usize>::new().range(1..))]
+    pub bench: Option<usize>,
+
+    /// Untimed runs of each step before benchmarking
+    #[clap(long, default_value_t = 3, requires = "bench")]
+    pub warmup: usize,
+
+    /// Also write the final state of a grid day's puzzle to PATH, as a PPM
+    /// image if it ends in `.ppm` or as text otherwise
+    #[clap(long, value_name = "PATH", conflicts_with = "bench")]
+    pub render: Option<String>,
+}
+
+#[derive(Args)]
+pub struct VerifyArgs {
+    /// Days to verify: a single day (`17`), a range (`5..12`, `5..=12`) or `all`
+    #[clap(default_value = "all")]
+    pub days: Vec<DaySelection>,
+
+    /// Directory containing inputs named `2023_01`, ... and their `.answers` manifests
+    #[clap(long, default_value = "inputs/real")]
+    pub inputs_dir: String,
+}
+
+impl VerifyArgs {
+    pub fn selected_days(&self) -> Vec<u8> {
+        selected_days(&self.days)
+    }
+
+    pub fn input_path(&self, day: u8) -> String {
+        default_input_path(&self.inputs_dir, day)
+    }
+}
+
+#[derive(Args)]
+pub struct AlmanacArgs {
+    /// Values or half-open ranges (`35`, `35..40`) to look up
+    #[clap(value_parser = parse_interval, required_unless_present = "table")]
+    pub values: Vec<Range<i64>>,
+
+    /// Almanac to query, or `-` for stdin
+    #[clap(short, long, default_value = "inputs/real/2023_05")]
     pub input: String,
+
+    /// Category the values are in, e.g. `soil` or `location`
+    #[clap(long, default_value = "location")]
+    pub from: String,
+
+    /// Print the composed seed-to-location table instead
+    #[clap(long)]
+    pub table: bool,
+}
+
+#[derive(Args)]
+pub struct WorkflowsArgs {
+    /// Workflows and parts list to use, or `-` for stdin
+    #[clap(short, long, default_value = "inputs/real/2023_19")]
+    pub input: String,
+
+    /// Check whether a part, written as in the parts list, is accepted
+    #[clap(long, value_name = "PART")]
+    pub check: Vec<String>,
+
+    /// Check every part in the parts list
+    #[clap(long)]
+    pub parts: bool,
+
+    /// Report unreachable workflows, dead or redundant rules and endless loops,
+    /// failing if there are any
+    #[clap(long, conflicts_with_all = ["check", "parts"])]
+    pub lint: bool,
+}
+
+#[derive(Args)]
+pub struct SpringsArgs {
+    /// Records written as in the puzzle input, e.g. `'???.### 1,1,3'`
+    #[clap(required = true)]
+    pub records: Vec<String>,
+
+    /// Unfold each record this many times first, as in part 2
+    #[clap(long, value_name = "N", default_value_t = 1)]
+    pub unfold: usize,
+
+    /// Most arrangements to list for each record
+    #[clap(long, value_name = "N", default_value_t = 20)]
+    pub limit: usize,
+
+    /// Draw N uniformly random arrangements instead of listing them in order
+    #[clap(long, value_name = "N")]
+    pub sample: Option<usize>,
+
+    /// Seed for `--sample`, to repeat a previous run
+    #[clap(long, requires = "sample")]
+    pub seed: Option<u64>,
+}
+
+#[derive(Args)]
+pub struct NonogramArgs {
+    /// Puzzle to solve, or `-` for stdin: a `rows:` section then a `columns:` section, with
+    /// one clue per line such as `3,1`, or `0` for an empty line
+    pub puzzle: String,
+
+    /// Most solutions to print; use 2 to check the solution is unique
+    #[clap(long, value_name = "N", default_value_t = 1)]
+    pub solutions: usize,
+
+    /// Read a picture of `#` and `.` cells instead, and print the puzzle it solves
+    #[clap(long, conflicts_with = "solutions")]
+    pub picture: bool,
+}
+
+#[derive(Args)]
+pub struct CrucibleArgs {
+    /// Map of heat loss to use, or `-` for stdin
+    #[clap(short, long, default_value = "inputs/real/2023_17")]
+    pub input: String,
+
+    /// Blocks the crucible must move in a straight line before turning or stopping
+    #[clap(long, value_name = "N", default_value_t = 1)]
+    pub min_run: usize,
+
+    /// Blocks the crucible can move in a straight line before it has to turn
+    #[clap(long, value_name = "N", default_value_t = 3)]
+    pub max_run: usize,
+
+    /// Allow the crucible to turn around
+    #[clap(long)]
+    pub reverse: bool,
+
+    /// Allow diagonal moves
+    #[clap(long)]
+    pub diagonals: bool,
+
+    /// Block to start from, as `row,column` counting from 0 [default: top-left]
+    #[clap(long, value_name = "ROW,COLUMN", value_parser = parse_pos)]
+    pub start: Option<Pos>,
+
+    /// Block to finish at, as `row,column` counting from 0 [default: bottom-right]
+    #[clap(long, value_name = "ROW,COLUMN", value_parser = parse_pos)]
+    pub goal: Option<Pos>,
+
+    /// Also write the route to PATH, as a PPM image if it ends in `.ppm` or as text otherwise
+    #[clap(long, value_name = "PATH")]
+    pub render: Option<String>,
+}
+
+#[derive(Args)]
+pub struct PulsesArgs {
+    /// Module network to use, or `-` for stdin
+    #[clap(short, long, default_value = "inputs/real/2023_20")]
+    pub input: String,
+
+    /// Times to press the button
+    #[clap(long, value_name = "N", default_value_t = 1000)]
+    pub presses: usize,
+
+    /// Print the pulses sent and flip-flops flipped by each press, not just the totals
+    #[clap(long)]
+    pub per_press: bool,
+
+    /// Find the binary counters which part 2's shortcut relies on, or which of
+    /// its assumptions the network breaks, then when `rx` first gets a low
+    /// pulse - simulating up to `--presses` presses if there's no shortcut
+    #[clap(long, conflicts_with_all = ["per_press", "trace"])]
+    pub analyse: bool,
+
+    /// Write every pulse to PATH, or `-` for stdout, one per line as
+    /// `<press> <from> -<low|high>-> <to>`
+    #[clap(long, value_name = "PATH")]
+    pub trace: Option<String>,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, ValueEnum)]
+pub enum GraphDay {
+    /// The day 20 module network
+    #[value(name = "20")]
+    Modules,
+    /// The day 23 trails between junctions
+    #[value(name = "23")]
+    Trails,
+    /// The day 25 wiring between components
+    #[value(name = "25")]
+    Wiring,
+}
+
+impl GraphDay {
+    pub fn day(self) -> u8 {
+        match self {
+            GraphDay::Modules => 20,
+            GraphDay::Trails => 23,
+            GraphDay::Wiring => 25,
+        }
+    }
+}
+
+#[derive(Args)]
+pub struct GraphArgs {
+    /// Day whose graph to write
+    pub day: GraphDay,
+
+    /// Input to use, or `-` for stdin [default: inputs/real/2023_<DAY>]
+    #[clap(short, long)]
+    pub input: Option<String>,
+
+    /// For day 23, follow the slopes one way as in part 1, or walk trails both ways as in part 2
+    #[clap(long, default_value_t = 1, value_parser = clap::value_parser!(u8).range(1..=2))]
+    pub part: u8,
+
+    /// Highlight the day 20 binary counters, the day 23 start and end, or the day 25 three-wire cut
+    #[clap(long)]
+    pub highlight: bool,
+
+    /// Write the graph to PATH instead of stdout
+    #[clap(short, long, value_name = "PATH")]
+    pub output: Option<String>,
+}
+
+fn parse_pos(s: &str) -> Result<Pos, String> {
+    let invalid = || format!("invalid position '{}', expected row,column", s);
+
+    let (y, x) = s.split_once(',').ok_or_else(invalid)?;
+    let y = y.trim().parse().map_err(|_| invalid())?;
+    let x = x.trim().parse().map_err(|_| invalid())?;
+    Ok(Pos::new(y, x))
+}
+
+fn parse_interval(s: &str) -> Result<Range<i64>, String> {
+    let number = |n: &str| {
+        n.parse::<i64>()
+            .map_err(|_| format!("invalid value '{}', expected a number", n))
+    };
+
+    let range = match s.split_once("..") {
+        Some((start, end)) => number(start)?..number(end)?,
+        None => {
+            let value = number(s)?;
+            value..value + 1
+        }
+    };
+
+    if range.is_empty() {
+        return Err(format!("empty range '{}'", s));
+    }
+    Ok(range)
+}
+
+fn selected_days(days: &[DaySelection]) -> Vec<u8> {
+    days.iter().flat_map(|sel| sel.days()).collect()
+}
+
+fn default_input_path(inputs_dir: &str, day: u8) -> String {
+    format!("{}/2023_{:02}", inputs_dir, day)
+}
+
+impl RunArgs {
+    pub fn selected_days(&self) -> Vec<u8> {
+        selected_days(&self.days)
+    }
+
+    pub fn bench_options(&self) -> Option<BenchOptions> {
+        self.bench.map(|iterations| BenchOptions {
+            warmup: self.warmup,
+            iterations,
+        })
+    }
+
+    pub fn input_paths(&self, day: u8) -> Vec<String> {
+        if self.input.is_empty() {
+            vec![default_input_path(&self.inputs_dir, day)]
+        } else {
+            self.input.clone()
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DaySelection {
+    first: u8,
+    last: u8,
+}
+
+impl DaySelection {
+    pub fn days(&self) -> RangeInclusive<u8> {
+        self.first..=self.last
+    }
+}
+
+fn parse_day(s: &str) -> Result<u8, String> {
+    match s.parse() {
+        Ok(day) if (1..=days::NUM_DAYS).contains(&day) => Ok(day),
+        _ => Err(format!(
+            "invalid day '{}', expected a number from 1 to {}",
+            s,
+            days::NUM_DAYS
+        )),
+    }
+}
+
+fn parse_bound(s: &str, default: u8) -> Result<u8, String> {
+    if s.is_empty() {
+        Ok(default)
+    } else {
+        parse_day(s)
+    }
+}
+
+impl FromStr for DaySelection {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (first, last) = if s == "all" {
+            (1, days::NUM_DAYS)
+        } else if let Some((first, last)) = s.split_once("..=") {
+            (parse_bound(first, 1)?, parse_bound(last, days::NUM_DAYS)?)
+        } else if let Some((first, last)) = s.split_once("..") {
+            // Exclusive end, as for rust ranges.
+            let first = parse_bound(first, 1)?;
+            let last = if last.is_empty() {
+                days::NUM_DAYS
+            } else {
+                parse_day(last)? - 1
+            };
+            (first, last)
+        } else {
+            let day = parse_day(s)?;
+            (day, day)
+        };
+
+        if first > last {
+            return Err(format!("empty day range '{}'", s));
+        }
+
+        Ok(DaySelection { first, last })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn days(s: &str) -> Vec<u8> {
+        s.parse::<DaySelection>().unwrap().days().collect()
+    }
+
+    #[test]
+    fn test_single_day() {
+        assert_eq!(days("17"), vec![17]);
+        assert_eq!(days("05"), vec![5]);
+    }
+
+    #[test]
+    fn test_all_days() {
+        assert_eq!(days("all"), (1..=25).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_day_ranges() {
+        assert_eq!(days("5..8"), vec![5, 6, 7]);
+        assert_eq!(days("5..=8"), vec![5, 6, 7, 8]);
+        assert_eq!(days("23.."), vec![23, 24, 25]);
+        assert_eq!(days("..=2"), vec![1, 2]);
+    }
+
+    #[test]
+    fn test_invalid_days() {
+        assert!("0".parse::<DaySelection>().is_err());
+        assert!("26".parse::<DaySelection>().is_err());
+        assert!("8..5".parse::<DaySelection>().is_err());
+        assert!("5..5".parse::<DaySelection>().is_err());
+        assert!("foo".parse::<DaySelection>().is_err());
+    }
+
+    #[test]
+    fn test_intervals() {
+        assert_eq!(parse_interval("35"), Ok(35..36));
+        assert_eq!(parse_interval("35..40"), Ok(35..40));
+        assert!(parse_interval("40..35").is_err());
+        assert!(parse_interval("35..").is_err());
+    }
+
+    #[test]
+    fn test_positions() {
+        assert_eq!(parse_pos("3,12"), Ok(Pos::new(3, 12)));
+        assert_eq!(parse_pos("3, 12"), Ok(Pos::new(3, 12)));
+        assert!(parse_pos("3").is_err());
+        assert!(parse_pos("-1,2").is_err());
+    }
+
+    #[test]
+    fn test_bench_runs() {
+        let cli = Cli::try_parse_from(["aoc", "run", "1", "--bench", "5"]).unwrap();
+        let Command::Run(args) = cli.command else {
+            panic!("not a run command");
+        };
+        assert_eq!(args.bench, Some(5));
+
+        assert!(Cli::try_parse_from(["aoc", "run", "1", "--bench", "0"]).is_err());
+    }
 }
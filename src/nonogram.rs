@@ -0,0 +1,289 @@
+use crate::days::day12;
+use crate::grid_util::{render_text, Grid, Pos};
+use crate::parse_util::{parse_number, ParseError};
+use std::collections::VecDeque;
+use std::fmt;
+use std::str::FromStr;
+
+pub const FILLED: u8 = b'#';
+pub const EMPTY: u8 = b'.';
+pub const UNKNOWN: u8 = b'?';
+
+/// A nonogram puzzle: the lengths of the runs of filled cells in each row,
+/// from left to right, and in each column, from top to bottom.
+///
+/// Each row and column is solved as a day 12 spring record, with filled cells
+/// as damaged springs.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Nonogram {
+    rows: Vec<Vec<usize>>,
+    columns: Vec<Vec<usize>>,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Line {
+    Row(usize),
+    Column(usize),
+}
+
+impl Nonogram {
+    pub fn new(rows: Vec<Vec<usize>>, columns: Vec<Vec<usize>>) -> Nonogram {
+        Nonogram { rows, columns }
+    }
+
+    /// The puzzle whose solution is `picture`, a grid of `FILLED` and `EMPTY` cells.
+    pub fn from_picture(picture: &Grid<u8>) -> Nonogram {
+        let clue = |cells: Vec<u8>| {
+            cells
+                .split(|&cell| cell != FILLED)
+                .filter(|run| !run.is_empty())
+                .map(|run| run.len())
+                .collect()
+        };
+
+        Nonogram {
+            rows: (0..picture.rows())
+                .map(|y| clue(picture.as_array().row(y).to_vec()))
+                .collect(),
+            columns: (0..picture.columns())
+                .map(|x| clue(picture.as_array().column(x).to_vec()))
+                .collect(),
+        }
+    }
+
+    /// Clues for each row, from top to bottom.
+    pub fn row_clues(&self) -> &[Vec<usize>] {
+        &self.rows
+    }
+
+    /// Clues for each column, from left to right.
+    pub fn column_clues(&self) -> &[Vec<usize>] {
+        &self.columns
+    }
+
+    /// Number of (rows, columns).
+    pub fn dim(&self) -> (usize, usize) {
+        (self.rows.len(), self.columns.len())
+    }
+
+    /// Some solution to the puzzle, if it has one.
+    pub fn solve(&self) -> Option<Grid<u8>> {
+        self.solutions(1).pop()
+    }
+
+    /// Up to `limit` distinct solutions, so `solutions(2)` shows whether the
+    /// solution is unique.
+    pub fn solutions(&self, limit: usize) -> Vec<Grid<u8>> {
+        let mut grid = Grid::from_elem(self.dim(), UNKNOWN);
+        let mut solutions = vec![];
+
+        let lines = (0..self.rows.len())
+            .map(Line::Row)
+            .chain((0..self.columns.len()).map(Line::Column));
+        if limit > 0 && self.propagate(&mut grid, lines) {
+            self.search(grid, limit, &mut solutions);
+        }
+
+        solutions
+    }
+
+    // Guesses the first unknown cell each way, backtracking once line solving gets stuck
+    fn search(&self, grid: Grid<u8>, limit: usize, solutions: &mut Vec<Grid<u8>>) {
+        let Some(pos) = grid.find(|&cell| cell == UNKNOWN) else {
+            solutions.push(grid);
+            return;
+        };
+
+        for guess in [FILLED, EMPTY] {
+            let mut next = grid.clone();
+            next[pos] = guess;
+
+            if self.propagate(&mut next, [Line::Row(pos.y), Line::Column(pos.x)]) {
+                self.search(next, limit, solutions);
+            }
+            if solutions.len() >= limit {
+                return;
+            }
+        }
+    }
+
+    // Line solves until nothing changes, starting from `lines`. Returns false
+    // if some line has no arrangements left.
+    fn propagate(&self, grid: &mut Grid<u8>, lines: impl IntoIterator<Item = Line>) -> bool {
+        let (rows, columns) = self.dim();
+        let mut queued = vec![false; rows + columns];
+        let index = |line| match line {
+            Line::Row(y) => y,
+            Line::Column(x) => rows + x,
+        };
+
+        let mut queue = VecDeque::new();
+        for line in lines {
+            queued[index(line)] = true;
+            queue.push_back(line);
+        }
+
+        while let Some(line) = queue.pop_front() {
+            queued[index(line)] = false;
+
+            let (positions, clue) = match line {
+                Line::Row(y) => (
+                    (0..columns).map(|x| Pos::new(y, x)).collect::<Vec<_>>(),
+                    &self.rows[y],
+                ),
+                Line::Column(x) => (
+                    (0..rows).map(|y| Pos::new(y, x)).collect(),
+                    &self.columns[x],
+                ),
+            };
+
+            let cells = positions.iter().map(|&pos| grid[pos]).collect();
+            let Some(resolved) = day12::resolve_springs(&day12::Data::new(cells, clue.clone()))
+            else {
+                return false;
+            };
+
+            for (&pos, cell) in positions.iter().zip(resolved) {
+                if grid[pos] == cell {
+                    continue;
+                }
+                grid[pos] = cell;
+
+                // The line crossing this cell may now have fewer arrangements
+                let crossing = match line {
+                    Line::Row(_) => Line::Column(pos.x),
+                    Line::Column(_) => Line::Row(pos.y),
+                };
+                if !queued[index(crossing)] {
+                    queued[index(crossing)] = true;
+                    queue.push_back(crossing);
+                }
+            }
+        }
+
+        true
+    }
+}
+
+/// Renders a (possibly partial) solution, one row per line.
+pub fn render_picture(grid: &Grid<u8>) -> String {
+    render_text(grid, |&cell| char::from(cell), None)
+}
+
+/// Parses a picture of `FILLED` and `EMPTY` cells, one row per line.
+pub fn parse_picture(raw_inp: &str) -> Result<Grid<u8>, ParseError> {
+    Grid::parse(raw_inp, "a cell (one of #.)", |b| {
+        [FILLED, EMPTY].contains(&b).then_some(b)
+    })
+}
+
+fn parse_clue(line: &str) -> Result<Vec<usize>, ParseError> {
+    let clue = line
+        .split(',')
+        .map(|n| parse_number(n.trim()))
+        .collect::<Result<Vec<usize>, _>>()?;
+
+    // A single 0 is an empty line
+    Ok(clue.into_iter().filter(|&n| n > 0).collect())
+}
+
+/// The text format is a `rows:` section followed by a `columns:` section,
+/// each with one clue per line written as comma-separated run lengths, or `0`
+/// for a line with no filled cells. Blank lines are ignored.
+impl FromStr for Nonogram {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, ParseError> {
+        let mut lines = s.lines().map(str::trim).filter(|line| !line.is_empty());
+
+        match lines.next() {
+            Some("rows:") => {}
+            other => return Err(ParseError::new(other.unwrap_or(s), "'rows:'")),
+        }
+
+        let mut rows = vec![];
+        let mut columns = None;
+        for line in lines {
+            match columns.as_mut() {
+                None if line == "columns:" => columns = Some(vec![]),
+                None => rows.push(parse_clue(line)?),
+                Some(columns) => columns.push(parse_clue(line)?),
+            }
+        }
+
+        let columns = columns.ok_or_else(|| ParseError::new(&s[s.len()..], "'columns:'"))?;
+        Ok(Nonogram { rows, columns })
+    }
+}
+
+impl fmt::Display for Nonogram {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let write_clues = |f: &mut fmt::Formatter, clues: &[Vec<usize>]| {
+            for clue in clues {
+                if clue.is_empty() {
+                    writeln!(f, "0")?;
+                } else {
+                    let runs = clue.iter().map(|n| n.to_string()).collect::<Vec<_>>();
+                    writeln!(f, "{}", runs.join(","))?;
+                }
+            }
+            Ok(())
+        };
+
+        writeln!(f, "rows:")?;
+        write_clues(f, &self.rows)?;
+        writeln!(f, "columns:")?;
+        write_clues(f, &self.columns)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PICTURE: &str = "\
+..#..
+.###.
+#####
+..#..
+..#..
+";
+
+    #[test]
+    fn test_solve() {
+        let puzzle = fixture!("nonogram").parse::<Nonogram>().unwrap();
+        assert_eq!(puzzle.dim(), (5, 5));
+        assert_eq!(
+            puzzle,
+            Nonogram::from_picture(&parse_picture(PICTURE).unwrap())
+        );
+        assert_eq!(puzzle.to_string().parse::<Nonogram>(), Ok(puzzle.clone()));
+
+        let solutions = puzzle.solutions(2);
+        assert_eq!(solutions.len(), 1);
+        assert_eq!(render_picture(&solutions[0]), PICTURE);
+    }
+
+    #[test]
+    fn test_backtracking() {
+        // Line solving alone can't make progress with a diagonal in either direction
+        let puzzle = Nonogram::new(vec![vec![1], vec![1]], vec![vec![1], vec![1]]);
+        let solutions = puzzle
+            .solutions(5)
+            .iter()
+            .map(render_picture)
+            .collect::<Vec<_>>();
+        assert_eq!(solutions, ["#.\n.#\n", ".#\n#.\n"]);
+
+        let impossible = Nonogram::new(vec![vec![2], vec![]], vec![vec![1], vec![]]);
+        assert_eq!(impossible.solve(), None);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!("columns:\n1\n".parse::<Nonogram>().is_err());
+        assert!("rows:\n1\n".parse::<Nonogram>().is_err());
+        assert!("rows:\n1,x\ncolumns:\n1\n".parse::<Nonogram>().is_err());
+        assert!(parse_picture("#?\n..\n").is_err());
+    }
+}
@@ -0,0 +1,176 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+/// Error returned when puzzle input doesn't match the expected format.
+///
+/// Parsers create these with [`ParseError::new`], pointing at the offending
+/// slice of the input. The slice is resolved to a line and column by
+/// [`ParseError::locate`] once the full input is known, so individual
+/// parsers never need to track positions themselves.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    expected: String,
+    // Address of the offending fragment - only meaningful relative to the
+    // input it was sliced from.
+    addr: usize,
+    location: Option<Location>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Location {
+    /// 1-based line number.
+    pub line: usize,
+    /// 1-based column number, in characters.
+    pub column: usize,
+    /// Full text of the offending line.
+    pub text: String,
+}
+
+impl ParseError {
+    /// Creates an error at the start of `fragment`, which must be a slice of
+    /// the puzzle input for [`ParseError::locate`] to find it.
+    pub fn new<S: AsRef<[u8]> + ?Sized>(fragment: &S, expected: impl Into<String>) -> ParseError {
+        ParseError {
+            expected: expected.into(),
+            addr: fragment.as_ref().as_ptr() as usize,
+            location: None,
+        }
+    }
+
+    /// Resolves the error's position within `raw_inp`.
+    pub fn locate(mut self, raw_inp: &str) -> ParseError {
+        let start = raw_inp.as_ptr() as usize;
+
+        if self.addr < start || self.addr > start + raw_inp.len() {
+            return self;
+        }
+
+        // Byte-based parsers may point into the middle of a character
+        let mut offset = self.addr - start;
+        while !raw_inp.is_char_boundary(offset) {
+            offset -= 1;
+        }
+        let before = &raw_inp[..offset];
+        let line_start = before.rfind('\n').map(|idx| idx + 1).unwrap_or(0);
+
+        self.location = Some(Location {
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+            text: raw_inp[line_start..]
+                .lines()
+                .next()
+                .unwrap_or_default()
+                .to_string(),
+        });
+        self
+    }
+
+    pub fn expected(&self) -> &str {
+        &self.expected
+    }
+
+    pub fn location(&self) -> Option<&Location> {
+        self.location.as_ref()
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.location {
+            Some(loc) => {
+                let gutter = " ".repeat(loc.line.to_string().len());
+                write!(
+                    f,
+                    "line {}, column {}: expected {}\n{} |\n{} | {}\n{} | {}^",
+                    loc.line,
+                    loc.column,
+                    self.expected,
+                    gutter,
+                    loc.line,
+                    loc.text,
+                    gutter,
+                    " ".repeat(loc.column - 1)
+                )
+            }
+            None => write!(f, "expected {}", self.expected),
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+/// Parses `s` as a number, reporting an error at `s` on failure.
+pub fn parse_number<T: FromStr>(s: &str) -> Result<T, ParseError> {
+    s.parse().map_err(|_| ParseError::new(s, "a number"))
+}
+
+/// Like [`str::split_once`], but reporting an error at the end of `s` if the
+/// delimiter is missing.
+pub fn split_once<'a>(s: &'a str, delimiter: &str) -> Result<(&'a str, &'a str), ParseError> {
+    s.split_once(delimiter)
+        .ok_or_else(|| ParseError::new(&s[s.len()..], format!("'{}'", delimiter)))
+}
+
+/// Strips `prefix` from `s`, reporting an error at `s` if it isn't present.
+pub fn strip_prefix<'a>(s: &'a str, prefix: &str) -> Result<&'a str, ParseError> {
+    s.strip_prefix(prefix)
+        .ok_or_else(|| ParseError::new(s, format!("'{}'", prefix)))
+}
+
+/// Strips `suffix` from `s`, reporting an error at the end of `s` if it isn't
+/// present.
+pub fn strip_suffix<'a>(s: &'a str, suffix: &str) -> Result<&'a str, ParseError> {
+    s.strip_suffix(suffix)
+        .ok_or_else(|| ParseError::new(&s[s.len()..], format!("'{}'", suffix)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "1 2 3\n4 x 6\n";
+
+    #[test]
+    fn test_locate() {
+        let line = INPUT.lines().nth(1).unwrap();
+        let err = parse_number::<i64>(&line[2..3]).unwrap_err().locate(INPUT);
+
+        let loc = err.location().unwrap();
+        assert_eq!((loc.line, loc.column), (2, 3));
+        assert_eq!(loc.text, "4 x 6");
+        assert_eq!(err.expected(), "a number");
+    }
+
+    #[test]
+    fn test_display() {
+        let err = split_once(INPUT.lines().next().unwrap(), ":")
+            .unwrap_err()
+            .locate(INPUT);
+
+        assert_eq!(
+            err.to_string(),
+            "line 1, column 6: expected ':'\n  |\n1 | 1 2 3\n  |      ^"
+        );
+    }
+
+    #[test]
+    fn test_locate_mid_character() {
+        let inp = "ab\ncé d\n";
+        let bytes = inp.as_bytes();
+        let err = ParseError::new(&bytes[5..], "a space").locate(inp);
+
+        // Points at the start of the character instead
+        let loc = err.location().unwrap();
+        assert_eq!((loc.line, loc.column), (2, 2));
+    }
+
+    #[test]
+    fn test_locate_foreign_fragment() {
+        let other = String::from("elsewhere");
+        let err = ParseError::new(&other, "something").locate(INPUT);
+
+        assert_eq!(err.location(), None);
+        assert_eq!(err.to_string(), "expected something");
+    }
+}
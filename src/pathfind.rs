@@ -0,0 +1,316 @@
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+
+/// A graph to search for shortest paths in, with non-negative integer edge costs.
+pub trait Graph {
+    type Node: Copy;
+
+    /// Upper bound on `index`.
+    fn num_nodes(&self) -> usize;
+
+    /// A distinct index below `num_nodes` for each node, used to keep costs in
+    /// a flat table rather than a hash map.
+    fn index(&self, node: Self::Node) -> usize;
+
+    /// Calls `visit` with each node reachable in one step from `node`, and the
+    /// cost of that step.
+    fn neighbours(&self, node: Self::Node, visit: impl FnMut(Self::Node, usize));
+
+    fn is_goal(&self, node: Self::Node) -> bool;
+
+    /// Lower bound on the cost from `node` to a goal, for A*. It must also be
+    /// consistent: no more than a step's cost plus the bound at the far end of it.
+    ///
+    /// The default of 0 makes the search plain Dijkstra.
+    fn heuristic(&self, _node: Self::Node) -> usize {
+        0
+    }
+}
+
+/// A priority queue which pops the item with the smallest priority first.
+pub trait MinPriorityQueue<T> {
+    fn push(&mut self, priority: usize, item: T);
+    fn pop(&mut self) -> Option<(usize, T)>;
+}
+
+/// A ring buffer of stacks, one per priority, which is approx 2x faster than
+/// a binary heap on 2023_17.
+///
+/// Only works when each priority pushed is less than `SIZE` above the smallest
+/// one in the queue - so for a shortest path search, `SIZE` must be larger than
+/// any step's cost plus the change in heuristic across it.
+pub struct BucketQueue<const SIZE: usize, T> {
+    smallest: usize,
+    len: usize,
+    stacks: [Vec<T>; SIZE],
+}
+
+impl<const SIZE: usize, T> BucketQueue<SIZE, T> {
+    const EMPTY_VEC: Vec<T> = vec![];
+
+    pub fn new() -> BucketQueue<SIZE, T> {
+        BucketQueue {
+            smallest: 0,
+            len: 0,
+            stacks: [Self::EMPTY_VEC; SIZE],
+        }
+    }
+}
+
+impl<const SIZE: usize, T> Default for BucketQueue<SIZE, T> {
+    fn default() -> Self {
+        BucketQueue::new()
+    }
+}
+
+impl<const SIZE: usize, T> MinPriorityQueue<T> for BucketQueue<SIZE, T> {
+    fn push(&mut self, priority: usize, item: T) {
+        // An empty queue can start from any priority, e.g. an A* start's heuristic
+        if self.len == 0 {
+            self.smallest = priority;
+        }
+        debug_assert!(priority >= self.smallest && priority < self.smallest + SIZE);
+
+        let idx = priority % SIZE;
+        self.stacks[idx].push(item);
+        self.len += 1;
+    }
+
+    fn pop(&mut self) -> Option<(usize, T)> {
+        if self.len == 0 {
+            return None;
+        }
+
+        loop {
+            if let Some(item) = self.stacks[self.smallest % SIZE].pop() {
+                self.len -= 1;
+                return Some((self.smallest, item));
+            }
+            self.smallest += 1;
+        }
+    }
+}
+
+struct HeapEntry<T> {
+    priority: usize,
+    item: T,
+}
+
+// Ordered by priority alone, reversed so that `BinaryHeap` pops the smallest
+impl<T> Ord for HeapEntry<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.priority.cmp(&self.priority)
+    }
+}
+
+impl<T> PartialOrd for HeapEntry<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T> PartialEq for HeapEntry<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.priority == other.priority
+    }
+}
+
+impl<T> Eq for HeapEntry<T> {}
+
+/// A binary heap, for searches with no small bound on step costs.
+pub struct HeapQueue<T> {
+    heap: BinaryHeap<HeapEntry<T>>,
+}
+
+impl<T> HeapQueue<T> {
+    pub fn new() -> HeapQueue<T> {
+        HeapQueue {
+            heap: BinaryHeap::new(),
+        }
+    }
+}
+
+impl<T> Default for HeapQueue<T> {
+    fn default() -> Self {
+        HeapQueue::new()
+    }
+}
+
+impl<T> MinPriorityQueue<T> for HeapQueue<T> {
+    fn push(&mut self, priority: usize, item: T) {
+        self.heap.push(HeapEntry { priority, item });
+    }
+
+    fn pop(&mut self) -> Option<(usize, T)> {
+        self.heap.pop().map(|entry| (entry.priority, entry.item))
+    }
+}
+
+// Dijkstra or A*, calling `improved` with each node and the node it was
+// reached from whenever a cheaper way to it is found
+fn search<G, Q>(
+    graph: &G,
+    starts: impl IntoIterator<Item = G::Node>,
+    mut queue: Q,
+    mut improved: impl FnMut(G::Node, G::Node),
+) -> Option<(usize, G::Node)>
+where
+    G: Graph,
+    Q: MinPriorityQueue<(usize, G::Node)>,
+{
+    let mut costs = vec![usize::MAX; graph.num_nodes()];
+
+    for start in starts {
+        costs[graph.index(start)] = 0;
+        queue.push(graph.heuristic(start), (0, start));
+    }
+
+    while let Some((_, (cost, node))) = queue.pop() {
+        // A cheaper way here was found after this entry was queued
+        if cost > costs[graph.index(node)] {
+            continue;
+        }
+        if graph.is_goal(node) {
+            return Some((cost, node));
+        }
+
+        graph.neighbours(node, |next, step_cost| {
+            let next_cost = cost + step_cost;
+            let idx = graph.index(next);
+
+            if next_cost < costs[idx] {
+                costs[idx] = next_cost;
+                improved(next, node);
+                queue.push(next_cost + graph.heuristic(next), (next_cost, next));
+            }
+        });
+    }
+
+    None
+}
+
+/// Finds the cheapest path from any of `starts` to a goal, returning its cost
+/// and the goal reached, using Dijkstra or A* depending on the graph's heuristic.
+///
+/// `queue` must be empty, and a `BucketQueue` must be large enough for the
+/// graph's largest step.
+pub fn shortest_path<G, Q>(
+    graph: &G,
+    starts: impl IntoIterator<Item = G::Node>,
+    queue: Q,
+) -> Option<(usize, G::Node)>
+where
+    G: Graph,
+    Q: MinPriorityQueue<(usize, G::Node)>,
+{
+    search(graph, starts, queue, |_, _| {})
+}
+
+/// As `shortest_path`, but returns every node along the path, from the start
+/// to the goal, instead of just the goal.
+pub fn shortest_route<G, Q>(
+    graph: &G,
+    starts: impl IntoIterator<Item = G::Node>,
+    queue: Q,
+) -> Option<(usize, Vec<G::Node>)>
+where
+    G: Graph,
+    Q: MinPriorityQueue<(usize, G::Node)>,
+{
+    let mut parents = vec![None; graph.num_nodes()];
+    let (cost, goal) = search(graph, starts, queue, |node, parent| {
+        parents[graph.index(node)] = Some(parent);
+    })?;
+
+    // Starts never have a parent, since nothing is cheaper than 0
+    let mut route = vec![goal];
+    while let Some(parent) = parents[graph.index(*route.last().unwrap())] {
+        route.push(parent);
+    }
+    route.reverse();
+
+    Some((cost, route))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Nodes on a line, where each node can step 1 forward for `cost` or 3 forward for 5
+    struct Line {
+        len: usize,
+        cost: usize,
+    }
+
+    impl Graph for Line {
+        type Node = usize;
+
+        fn num_nodes(&self) -> usize {
+            self.len
+        }
+
+        fn index(&self, node: usize) -> usize {
+            node
+        }
+
+        fn neighbours(&self, node: usize, mut visit: impl FnMut(usize, usize)) {
+            if node + 1 < self.len {
+                visit(node + 1, self.cost);
+            }
+            if node + 3 < self.len {
+                visit(node + 3, 5);
+            }
+        }
+
+        fn is_goal(&self, node: usize) -> bool {
+            node == self.len - 1
+        }
+
+        fn heuristic(&self, node: usize) -> usize {
+            (self.len - 1 - node) * self.cost.min(1)
+        }
+    }
+
+    #[test]
+    fn test_queues() {
+        let mut bucket = BucketQueue::<8, _>::new();
+        let mut heap = HeapQueue::new();
+        for (priority, item) in [(0, 'b'), (2, 'a'), (3, 'c'), (2, 'd')] {
+            bucket.push(priority, item);
+            heap.push(priority, item);
+        }
+
+        assert_eq!(bucket.pop(), Some((0, 'b')));
+        assert_eq!(heap.pop(), Some((0, 'b')));
+        bucket.push(4, 'e');
+        heap.push(4, 'e');
+
+        let rest = |queue: &mut dyn MinPriorityQueue<char>| {
+            std::iter::from_fn(|| queue.pop())
+                .map(|(priority, _)| priority)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(rest(&mut bucket), [2, 2, 3, 4]);
+        assert_eq!(rest(&mut heap), [2, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_shortest_path() {
+        // 2 jumps and 2 steps (14) are cheaper than 8 steps (16)
+        let graph = Line { len: 9, cost: 2 };
+        assert_eq!(
+            shortest_path(&graph, [0], BucketQueue::<8, _>::new()),
+            Some((14, 8))
+        );
+        assert_eq!(shortest_path(&graph, [0], HeapQueue::new()), Some((14, 8)));
+
+        let (cost, route) = shortest_route(&graph, [0], HeapQueue::new()).unwrap();
+        assert_eq!(cost, 14);
+        assert_eq!(route.len(), 5);
+        assert_eq!((route[0], route[4]), (0, 8));
+
+        let free = Line { len: 9, cost: 0 };
+        assert_eq!(shortest_path(&free, [0], HeapQueue::new()), Some((0, 8)));
+        assert_eq!(shortest_path(&free, [], HeapQueue::new()), None);
+    }
+}
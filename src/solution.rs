@@ -0,0 +1,169 @@
+use crate::bench::{measure, BenchOptions, Stats};
+use crate::grid_util::RenderFormat;
+use crate::parse_util::ParseError;
+use anyhow::{anyhow, Context, Result};
+use std::convert::Infallible;
+use std::fmt::Display;
+use std::time::{Duration, Instant};
+
+/// Common interface implemented by every day's puzzle.
+///
+/// Parsing is kept separate from solving so that callers can time, cache or
+/// reuse the parsed input between parts.
+pub trait Solution: Sync {
+    /// Parsed puzzle input. May borrow from the raw input string.
+    type Input<'a>;
+    type Answer1: Answer;
+    type Answer2: Answer;
+
+    fn parse<'a>(&self, raw_inp: &'a str) -> Result<Self::Input<'a>, ParseError>;
+
+    fn part1(&self, input: &Self::Input<'_>) -> Self::Answer1;
+
+    /// Whether the puzzle has a part 2, which every day but the last does.
+    fn has_part2(&self) -> bool {
+        true
+    }
+
+    /// The part 2 answer, or `None` if the puzzle has no part 2.
+    fn part2(&self, _input: &Self::Input<'_>) -> Option<Self::Answer2> {
+        None
+    }
+
+    /// Renders the final state of the puzzle's grid, for days which have one.
+    fn render(&self, _input: &Self::Input<'_>, _format: RenderFormat) -> Option<Vec<u8>> {
+        None
+    }
+}
+
+/// The answer to one part of a puzzle. Parts which can fail on some inputs
+/// answer with a `Result`, whose error says why there's no answer.
+pub trait Answer {
+    fn into_answer(self) -> Result<String>;
+}
+
+macro_rules! display_answer {
+    ($($t:ty),*) => {
+        $(
+            impl Answer for $t {
+                fn into_answer(self) -> Result<String> {
+                    Ok(self.to_string())
+                }
+            }
+        )*
+    };
+}
+
+display_answer!(i64, i128, u32, u64, usize, String);
+
+impl Answer for Infallible {
+    fn into_answer(self) -> Result<String> {
+        match self {}
+    }
+}
+
+impl<T: Display, E: Display> Answer for Result<T, E> {
+    fn into_answer(self) -> Result<String> {
+        self.map(|answer| answer.to_string())
+            .map_err(|e| anyhow!("{}", e))
+    }
+}
+
+/// Answers for one day, along with how long each step took.
+pub struct DayResult {
+    pub parse_time: Duration,
+    pub parts: Vec<PartResult>,
+}
+
+pub struct PartResult {
+    /// 1-based part number.
+    pub part: u8,
+    pub answer: String,
+    pub solve_time: Duration,
+}
+
+/// Timing statistics for one day, with one entry in `parts` per part.
+pub struct BenchResult {
+    pub parse: Stats,
+    pub parts: Vec<Stats>,
+}
+
+fn timed<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+    let start = Instant::now();
+    let res = f();
+    (res, start.elapsed())
+}
+
+/// Object-safe view of a [`Solution`], used by the runner's registry.
+pub trait Day: Sync {
+    /// Parses and solves the puzzle, returning one answer per part.
+    fn run(&self, raw_inp: &str) -> Result<DayResult>;
+
+    /// Repeatedly times parsing and each part separately.
+    fn bench(&self, raw_inp: &str, opts: BenchOptions) -> Result<BenchResult>;
+
+    /// Parses the puzzle and renders it, or returns `None` if the day has
+    /// nothing to render.
+    fn render(&self, raw_inp: &str, format: RenderFormat) -> Result<Option<Vec<u8>>>;
+}
+
+impl<S: Solution> Day for S {
+    fn run(&self, raw_inp: &str) -> Result<DayResult> {
+        let (input, parse_time) = timed(|| self.parse(raw_inp));
+        let input = input
+            .map_err(|e| e.locate(raw_inp))
+            .context("invalid input")?;
+
+        let (answer, solve_time) = timed(|| self.part1(&input));
+        let mut parts = vec![PartResult {
+            part: 1,
+            answer: answer.into_answer().context("can't solve part 1")?,
+            solve_time,
+        }];
+
+        if self.has_part2() {
+            let (answer, solve_time) = timed(|| self.part2(&input));
+            if let Some(answer) = answer {
+                parts.push(PartResult {
+                    part: 2,
+                    answer: answer.into_answer().context("can't solve part 2")?,
+                    solve_time,
+                });
+            }
+        }
+
+        Ok(DayResult { parse_time, parts })
+    }
+
+    fn bench(&self, raw_inp: &str, opts: BenchOptions) -> Result<BenchResult> {
+        // Parse once up-front so that invalid input is reported, not timed
+        let input = self
+            .parse(raw_inp)
+            .map_err(|e| e.locate(raw_inp))
+            .context("invalid input")?;
+
+        let (parse, _) = measure(opts, || self.parse(raw_inp));
+
+        let (stats, answer) = measure(opts, || self.part1(&input));
+        answer.into_answer().context("can't solve part 1")?;
+        let mut parts = vec![stats];
+
+        if self.has_part2() {
+            let (stats, answer) = measure(opts, || self.part2(&input));
+            if let Some(answer) = answer {
+                answer.into_answer().context("can't solve part 2")?;
+                parts.push(stats);
+            }
+        }
+
+        Ok(BenchResult { parse, parts })
+    }
+
+    fn render(&self, raw_inp: &str, format: RenderFormat) -> Result<Option<Vec<u8>>> {
+        let input = self
+            .parse(raw_inp)
+            .map_err(|e| e.locate(raw_inp))
+            .context("invalid input")?;
+        Ok(Solution::render(self, &input, format))
+    }
+}
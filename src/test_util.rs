@@ -0,0 +1,112 @@
+use crate::verify::read_answers;
+use std::env;
+use std::fmt::{Debug, Display};
+use std::fs;
+use std::path::Path;
+
+/// Set to make tests fail, rather than skip, when a real input or its answers
+/// are missing.
+const REQUIRE_REAL_INPUTS: &str = "AOC_REQUIRE_REAL_INPUTS";
+
+fn skip_or_panic<T>(what: &str, err: impl Display) -> Option<T> {
+    if env::var_os(REQUIRE_REAL_INPUTS).is_some() {
+        panic!("can't read {}: {}", what, err);
+    }
+    eprintln!("skipping: can't read {}", what);
+    None
+}
+
+/// Reads a personal input from `inputs/real` along with the expected answer to
+/// `part` from its answers manifest, or returns `None` if either isn't there.
+pub fn real_input(name: &str, part: usize) -> Option<(String, String)> {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("inputs/real")
+        .join(name)
+        .to_string_lossy()
+        .into_owned();
+
+    let inp = match fs::read_to_string(&path) {
+        Ok(inp) => inp,
+        Err(e) => return skip_or_panic(&path, e),
+    };
+
+    match read_answers(&path).map(|answers| answers.get(part - 1).cloned()) {
+        Ok(Some(answer)) => Some((inp, answer)),
+        Ok(None) => skip_or_panic(&format!("part {} answer for {}", part, path), "missing"),
+        Err(e) => skip_or_panic(&format!("answers for {}", path), e),
+    }
+}
+
+#[track_caller]
+pub fn check<T: Debug + PartialEq>(inp: &str, solve: impl FnOnce(&str) -> T, expected: T) {
+    assert_eq!(solve(inp), expected);
+}
+
+#[track_caller]
+pub fn check_real<T: Display>(inp: &str, solve: impl FnOnce(&str) -> T, expected: &str) {
+    assert_eq!(solve(inp).to_string(), expected);
+}
+
+/// The contents of a file in `tests/fixtures`, embedded at compile time:
+///
+/// ```ignore
+/// let data = parse(fixture!("2023_17")).unwrap();
+/// ```
+macro_rules! fixture {
+    ($name:literal) => {
+        include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/fixtures/",
+            $name
+        ))
+    };
+}
+
+/// Generates a module of tests for each group, all checking answers from the
+/// same solver. Example tests name a fixture in `tests/fixtures` and give the
+/// answer inline. Groups marked with a part also get a `real` test, checking
+/// the personal input against that part's answer in its manifest and skipping
+/// if either isn't available:
+///
+/// ```ignore
+/// aoc_tests! {
+///     real_input: "2023_17";
+///
+///     p1 (part 1): |inp| calculate_p1(&parse(inp).unwrap()) => {
+///         example("2023_17") => 102,
+///     }
+/// }
+/// ```
+macro_rules! aoc_tests {
+    (
+        real_input: $real:literal;
+        $(
+            $group:ident $((part $part:literal))?: $solve:expr => {
+                $($test:ident($fixture:literal) => $answer:expr,)*
+            }
+        )*
+    ) => {
+        $(
+            mod $group {
+                use super::*;
+
+                $(
+                    #[test]
+                    fn $test() {
+                        let inp = fixture!($fixture);
+                        $crate::test_util::check(inp, $solve, $answer);
+                    }
+                )*
+
+                $(
+                    #[test]
+                    fn real() {
+                        if let Some((inp, expected)) = $crate::test_util::real_input($real, $part) {
+                            $crate::test_util::check_real(&inp, $solve, &expected);
+                        }
+                    }
+                )?
+            }
+        )*
+    };
+}
@@ -0,0 +1,72 @@
+use std::fs;
+use std::io;
+
+/// Path of the answers manifest for the input at `input_path`.
+///
+/// A manifest holds the expected answer to each part on its own line, in the
+/// same format as `aoc run` prints them.
+pub fn answers_path(input_path: &str) -> String {
+    format!("{}.answers", input_path)
+}
+
+pub fn parse_answers(manifest: &str) -> Vec<String> {
+    manifest
+        .lines()
+        .map(|line| line.trim().to_string())
+        .filter(|line| !line.is_empty())
+        .collect()
+}
+
+pub fn read_answers(input_path: &str) -> io::Result<Vec<String>> {
+    fs::read_to_string(answers_path(input_path)).map(|manifest| parse_answers(&manifest))
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Verdict {
+    Pass,
+    Fail {
+        expected: String,
+        actual: String,
+    },
+    /// The manifest has no answer for this part.
+    Missing,
+}
+
+/// Compares the answer to each part against the manifest.
+pub fn check_answers(expected: &[String], actual: &[String]) -> Vec<Verdict> {
+    actual
+        .iter()
+        .enumerate()
+        .map(|(idx, actual)| match expected.get(idx) {
+            None => Verdict::Missing,
+            Some(expected) if expected == actual => Verdict::Pass,
+            Some(expected) => Verdict::Fail {
+                expected: expected.clone(),
+                actual: actual.clone(),
+            },
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_answers() {
+        let expected = parse_answers("142\n 281 \n\n");
+        assert_eq!(expected, vec!["142", "281"]);
+
+        let verdicts = check_answers(&expected[..1], &["142".to_string(), "281".to_string()]);
+        assert_eq!(verdicts, vec![Verdict::Pass, Verdict::Missing]);
+
+        let verdicts = check_answers(&expected, &["142".to_string(), "280".to_string()]);
+        assert_eq!(
+            verdicts[1],
+            Verdict::Fail {
+                expected: "281".to_string(),
+                actual: "280".to_string()
+            }
+        );
+    }
+}